@@ -3,35 +3,124 @@
 
 pub mod prelude {
     pub use glfw::{
-        Action, Context, Glfw, Key, Modifiers, MouseButton, Scancode, Window, WindowEvent,
+        Action, Context, ContextReleaseBehavior, Glfw, Key, Modifiers, MouseButton, Scancode,
+        Window, WindowEvent, WindowHint,
     };
     pub use glfw_ext::WindowExt;
 
-    pub use super::{App, AppOptions, EventReceiver};
+    pub use super::{
+        primary_monitor_work_area, App, AppInitError, AppOptions, AppOptionsBuilder, AppRunner,
+        EventReceiver,
+    };
 }
 
-pub use glfw::{Action, Context, Glfw, Key, Modifiers, MouseButton, Scancode, Window, WindowEvent};
+pub use glfw::{
+    Action, Context, ContextReleaseBehavior, Glfw, Key, Modifiers, MouseButton, Scancode, Window,
+    WindowEvent, WindowHint,
+};
 
+use std::cell::RefCell;
 use std::error;
+use std::ops::ControlFlow;
+use std::rc::Rc;
 use std::sync::mpsc::Receiver;
+use std::thread;
+use std::time::Duration;
 
 #[cfg(debug_assertions)]
 use std::iter;
 
-use glfw::{OpenGlProfileHint, WindowHint, WindowMode};
+use glfw::{OpenGlProfileHint, WindowMode};
 use glfw_ext::WindowExt;
+use thiserror::Error;
 
 use crate::debug_output::{init_debug_output, is_debug_output_supported};
 use crate::gl45::RenderingContext;
+use crate::input::Input;
 
 pub type EventReceiver = Receiver<(f64, WindowEvent)>;
 
+/// Prefer constructing `AppOptions` using [`AppOptions::builder()`],
+/// as new fields may be added in the future.
+///
+/// ```no_run
+/// # use ren::prelude::*;
+/// let opts = AppOptions::builder()
+///     .title("My App")
+///     .window_size((1280, 720))
+///     .vsync(false)
+///     .build();
+/// ```
 #[derive(Clone, Debug)]
 pub struct AppOptions<'a> {
     pub title: &'a str,
     pub window_size: (u32, u32),
+    /// Position of the top-left corner of the window's content area, e.g.
+    /// restored from a previous run using [`Window::get_pos()`]. If `None`,
+    /// then the window is centered on the dominant monitor.
+    pub window_pos: Option<(i32, i32)>,
     pub gl_version: (u32, u32),
+    /// If the requested [`gl_version`](Self::gl_version) is not available,
+    /// then try progressively lower versions down to `min_gl_version`.
+    /// If `None`, then only the requested version is tried.
+    ///
+    /// The obtained version can be queried using
+    /// [`RenderingContext::gl_version()`]. Note that most wrappers
+    /// require OpenGL 4.5, i.e. lower versions are only useful when
+    /// the app falls back to its own rendering paths.
+    pub min_gl_version: Option<(u32, u32)>,
     pub gl_debug_output: bool,
+    /// Enable or disable vsync, or `None` to keep the default
+    /// swap interval of the driver, which is the default.
+    pub vsync: Option<bool>,
+    /// Number of screen updates to wait for before swapping buffers,
+    /// which overrides [`vsync`](Self::vsync) if `Some`, i.e. `0`
+    /// disables vsync, and `1` enables vsync.
+    ///
+    /// A negative value `-n` enables adaptive vsync waiting for `n`
+    /// screen updates, i.e. a late frame is swapped immediately, tearing
+    /// instead of waiting for the next screen update. This requires
+    /// `WGL_EXT_swap_control_tear` or `GLX_EXT_swap_control_tear`,
+    /// and behaves like vsync otherwise.
+    pub swap_interval: Option<i32>,
+    /// Limit the frame rate, by sleeping the remainder of each frame,
+    /// e.g. to save power when vsync is unavailable or disabled.
+    /// `None` and `Some(0)` both leave the frame rate unlimited.
+    pub max_fps: Option<u32>,
+    /// Set the viewport to the whole framebuffer, when it is resized.
+    /// Disable to manage the viewport entirely using
+    /// [`RenderingContext::set_viewport()`], e.g. when rendering to
+    /// a fixed-size offscreen target.
+    pub auto_viewport: bool,
+    /// Lock the aspect ratio of the window to `(numerator, denominator)`.
+    pub aspect_ratio: Option<(u32, u32)>,
+    pub min_window_size: Option<(u32, u32)>,
+    pub max_window_size: Option<(u32, u32)>,
+    /// Whether the pipeline is flushed, when the context is released
+    /// from being current. If `None`, then GLFW's default is used.
+    pub context_release_behavior: Option<ContextReleaseBehavior>,
+    /// Enable GLFW's sticky keys input mode, i.e. [`Window::get_key()`]
+    /// returns [`Action::Press`] until it has been called, even if the
+    /// key was released before, so short presses are not missed when
+    /// polling keys once per frame.
+    ///
+    /// This does not affect [`WindowEvent::Key`], which still reports
+    /// [`Action::Repeat`] while a key is held, see [`App::on_event()`].
+    pub sticky_keys: bool,
+    /// Additional GLFW window hints, not covered by other options, e.g.
+    /// [`WindowHint::TransparentFramebuffer`] or [`WindowHint::Floating`].
+    ///
+    /// These are applied after the built-in hints, so they take precedence,
+    /// except for the context version and debug context hints, which are
+    /// derived from [`gl_version`](Self::gl_version) and
+    /// [`gl_debug_output`](Self::gl_debug_output).
+    pub extra_hints: Vec<WindowHint>,
+    /// Called for GLFW errors reported after the window was created.
+    /// If `None`, then errors are ignored.
+    ///
+    /// Errors while creating the window are not reported here, but
+    /// returned as [`AppInitError`] instead.
+    pub glfw_error_callback: Option<fn(glfw::Error, &str)>,
 }
 
 impl Default for AppOptions<'static> {
@@ -39,8 +128,21 @@ impl Default for AppOptions<'static> {
         Self {
             title: env!("CARGO_PKG_NAME"),
             window_size: Self::DEFAULT_WINDOW_SIZE,
+            window_pos: None,
             gl_version: Self::DEFAULT_GL_VERSION,
+            min_gl_version: None,
             gl_debug_output: Self::DEFAULT_GL_DEBUG_OUTPUT,
+            vsync: Self::DEFAULT_VSYNC,
+            swap_interval: None,
+            max_fps: None,
+            auto_viewport: Self::DEFAULT_AUTO_VIEWPORT,
+            aspect_ratio: None,
+            min_window_size: None,
+            max_window_size: None,
+            context_release_behavior: None,
+            sticky_keys: false,
+            extra_hints: Vec::new(),
+            glfw_error_callback: None,
         }
     }
 }
@@ -50,6 +152,217 @@ impl AppOptions<'static> {
     pub const DEFAULT_WINDOW_SIZE: (u32, u32) = (856, 482);
     pub const DEFAULT_GL_VERSION: (u32, u32) = (4, 5);
     pub const DEFAULT_GL_DEBUG_OUTPUT: bool = cfg!(debug_assertions);
+    pub const DEFAULT_VSYNC: Option<bool> = None;
+    pub const DEFAULT_AUTO_VIEWPORT: bool = true;
+}
+
+impl<'a> AppOptions<'a> {
+    #[inline]
+    pub fn builder() -> AppOptionsBuilder<'a> {
+        AppOptionsBuilder::new()
+    }
+}
+
+/// Builder for [`AppOptions`], see [`AppOptions::builder()`].
+#[derive(Clone, Debug)]
+pub struct AppOptionsBuilder<'a> {
+    opts: AppOptions<'a>,
+}
+
+impl<'a> AppOptionsBuilder<'a> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            opts: AppOptions::default(),
+        }
+    }
+
+    #[inline]
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.opts.title = title;
+        self
+    }
+
+    #[inline]
+    pub fn window_size(mut self, window_size: (u32, u32)) -> Self {
+        self.opts.window_size = window_size;
+        self
+    }
+
+    #[inline]
+    pub fn window_pos(mut self, window_pos: Option<(i32, i32)>) -> Self {
+        self.opts.window_pos = window_pos;
+        self
+    }
+
+    #[inline]
+    pub fn gl_version(mut self, gl_version: (u32, u32)) -> Self {
+        self.opts.gl_version = gl_version;
+        self
+    }
+
+    #[inline]
+    pub fn min_gl_version(mut self, min_gl_version: Option<(u32, u32)>) -> Self {
+        self.opts.min_gl_version = min_gl_version;
+        self
+    }
+
+    #[inline]
+    pub fn gl_debug_output(mut self, gl_debug_output: bool) -> Self {
+        self.opts.gl_debug_output = gl_debug_output;
+        self
+    }
+
+    #[inline]
+    pub fn vsync(mut self, vsync: bool) -> Self {
+        self.opts.vsync = Some(vsync);
+        self
+    }
+
+    #[inline]
+    pub fn swap_interval(mut self, swap_interval: Option<i32>) -> Self {
+        self.opts.swap_interval = swap_interval;
+        self
+    }
+
+    #[inline]
+    pub fn max_fps(mut self, max_fps: Option<u32>) -> Self {
+        self.opts.max_fps = max_fps;
+        self
+    }
+
+    #[inline]
+    pub fn auto_viewport(mut self, auto_viewport: bool) -> Self {
+        self.opts.auto_viewport = auto_viewport;
+        self
+    }
+
+    #[inline]
+    pub fn aspect_ratio(mut self, aspect_ratio: Option<(u32, u32)>) -> Self {
+        self.opts.aspect_ratio = aspect_ratio;
+        self
+    }
+
+    #[inline]
+    pub fn min_window_size(mut self, min_window_size: Option<(u32, u32)>) -> Self {
+        self.opts.min_window_size = min_window_size;
+        self
+    }
+
+    #[inline]
+    pub fn max_window_size(mut self, max_window_size: Option<(u32, u32)>) -> Self {
+        self.opts.max_window_size = max_window_size;
+        self
+    }
+
+    #[inline]
+    pub fn context_release_behavior(
+        mut self,
+        context_release_behavior: Option<ContextReleaseBehavior>,
+    ) -> Self {
+        self.opts.context_release_behavior = context_release_behavior;
+        self
+    }
+
+    #[inline]
+    pub fn sticky_keys(mut self, sticky_keys: bool) -> Self {
+        self.opts.sticky_keys = sticky_keys;
+        self
+    }
+
+    /// Add an additional GLFW window hint, see [`AppOptions::extra_hints`].
+    #[inline]
+    pub fn extra_hint(mut self, hint: WindowHint) -> Self {
+        self.opts.extra_hints.push(hint);
+        self
+    }
+
+    #[inline]
+    pub fn glfw_error_callback(mut self, callback: Option<fn(glfw::Error, &str)>) -> Self {
+        self.opts.glfw_error_callback = callback;
+        self
+    }
+
+    #[inline]
+    pub fn build(self) -> AppOptions<'a> {
+        self.opts
+    }
+}
+
+impl Default for AppOptionsBuilder<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> From<AppOptionsBuilder<'a>> for AppOptions<'a> {
+    #[inline]
+    fn from(builder: AppOptionsBuilder<'a>) -> Self {
+        builder.build()
+    }
+}
+
+/// Error returned when initializing GLFW, creating the window,
+/// or [`App::init()`] fails, including the relevant [`AppOptions`]
+/// that were in effect.
+#[derive(Error, Debug)]
+pub enum AppInitError {
+    #[error("initializing glfw failed: {0}")]
+    Glfw(#[from] glfw::InitError),
+    /// The requested [`AppOptions::gl_version`] is not available,
+    /// e.g. on older hardware, in which case a lower version must
+    /// be requested.
+    ///
+    /// If [`AppOptions::min_gl_version`] is set, then all versions
+    /// from `gl_version` down to `min_gl_version` were tried.
+    #[error(
+        "OpenGL {} is not supported, try requesting a lower `gl_version`: {description}",
+        version_range(*.gl_version, *.min_gl_version)
+    )]
+    UnsupportedVersion {
+        gl_version: (u32, u32),
+        /// The lowest version tried, which is `gl_version`
+        /// if no fallback versions were tried.
+        min_gl_version: (u32, u32),
+        description: String,
+    },
+    #[error("creating window {title:?} with OpenGL {}.{} failed: {description}", .gl_version.0, .gl_version.1)]
+    WindowCreation {
+        title: String,
+        /// The version requested when creating the window failed,
+        /// which may be a fallback version.
+        gl_version: (u32, u32),
+        description: String,
+    },
+    #[error("initializing app {title:?} with OpenGL {}.{} failed: {source}", .gl_version.0, .gl_version.1)]
+    App {
+        title: String,
+        /// The version of the created context, which is lower than the
+        /// requested [`AppOptions::gl_version`] if a fallback was used.
+        gl_version: (u32, u32),
+        #[source]
+        source: Box<dyn error::Error>,
+    },
+}
+
+impl AppInitError {
+    fn app(opts: &AppOptions<'_>, gl_version: (u32, u32), source: Box<dyn error::Error>) -> Self {
+        Self::App {
+            title: opts.title.to_owned(),
+            gl_version,
+            source,
+        }
+    }
+}
+
+/// Formats e.g. `4.5`, or `4.5 to 3.3` if `min` is lower than `max`.
+fn version_range(max: (u32, u32), min: (u32, u32)) -> String {
+    if min == max {
+        format!("{}.{}", max.0, max.1)
+    } else {
+        format!("{}.{} to {}.{}", max.0, max.1, min.0, min.1)
+    }
 }
 
 #[allow(unused_variables)]
@@ -57,9 +370,71 @@ pub trait App<'gl>: Sized {
     type Err: Into<Box<dyn error::Error>>;
 
     fn init(ctx: &mut RenderingContext<'gl>) -> Result<Self, Self::Err>;
-    fn update(&mut self, ctx: &mut RenderingContext<'gl>, wnd: &mut Window) {}
+    fn update(&mut self, ctx: &mut RenderingContext<'gl>, wnd: &mut Window, input: &Input) {}
     fn draw(&mut self, ctx: &mut RenderingContext<'gl>, wnd: &Window);
+
+    /// Called for each event polled.
+    ///
+    /// While a key is held, [`WindowEvent::Key`] is reported repeatedly
+    /// with [`Action::Repeat`] after the initial [`Action::Press`]. To
+    /// toggle something once per key press, match only `Action::Press`:
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # struct MyApp { wireframe: bool }
+    /// # impl MyApp {
+    /// # fn on_event(&mut self, evt: WindowEvent) {
+    /// if let WindowEvent::Key(Key::F, _, Action::Press, _) = evt {
+    ///     self.wireframe = !self.wireframe;
+    /// }
+    /// # }
+    /// # }
+    /// ```
+    ///
+    /// When the window loses or gains focus, [`WindowEvent::Focus`]
+    /// is reported, e.g. to pause the app and release the cursor:
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # struct MyApp { paused: bool }
+    /// # impl MyApp {
+    /// # fn on_event(&mut self, evt: WindowEvent, wnd: &mut Window) {
+    /// if let WindowEvent::Focus(focused) = evt {
+    ///     self.paused = !focused;
+    ///     if !focused {
+    ///         wnd.set_cursor_mode(glfw::CursorMode::Normal);
+    ///     }
+    /// }
+    /// # }
+    /// # }
+    /// ```
+    ///
+    /// Then skip advancing the simulation in [`update()`](Self::update)
+    /// while paused.
+    ///
+    /// Similarly, [`WindowEvent::Iconify`] is reported when the window
+    /// is minimized or restored, e.g. to skip expensive rendering in
+    /// [`draw()`](Self::draw) while minimized.
     fn on_event(&mut self, evt: WindowEvent, ctx: &mut RenderingContext<'gl>, wnd: &mut Window) {}
+
+    /// Called once when the main loop exits, e.g. when the window is
+    /// closed, while the OpenGL context is still current, e.g. to save
+    /// settings or delete external resources. When using [`AppRunner`],
+    /// this is called when the `AppRunner` is dropped.
+    ///
+    /// This is not called if the main loop exits due to a panic.
+    fn on_exit(&mut self, ctx: &mut RenderingContext<'gl>) {}
+
+    /// Called once per frame with all events polled that frame, after
+    /// [`on_event()`](Self::on_event) has been called for each of them,
+    /// e.g. to coalesce mouse movement or detect bursts of events.
+    fn on_events(
+        &mut self,
+        events: &[WindowEvent],
+        ctx: &mut RenderingContext<'gl>,
+        wnd: &mut Window,
+    ) {
+    }
 }
 
 /// This is a helper trait, as it is currently not
@@ -148,36 +523,134 @@ pub fn _run_app_with<F>(opts: AppOptions<'_>, f: F) -> Result<(), Box<dyn error:
 where
     F: for<'gl> InitApp<'gl>,
 {
-    let (mut glfw, mut wnd, events) = init(opts, true);
-    // Safety: OpenGL context is current and `RenderingContext` cannot escape the closure
-    let mut ctx = unsafe { RenderingContext::new() };
-    let mut app = f.init(&mut ctx).map_err(Into::into)?;
+    let mut runner = AppRunner::new(opts, f)?;
+    while runner.step().is_continue() {}
+    Ok(())
+}
 
-    'main: while !wnd.should_close() {
-        glfw.poll_events();
+/// Runs an [`App`] one iteration at a time, instead of taking
+/// over the thread like [`run!`](crate::run!) does. This allows
+/// integrating the main loop with other event sources.
+///
+/// Like [`_run_app`], `init` must be a function and not a closure:
+///
+/// ```no_run
+/// # use std::convert::Infallible;
+/// use ren::prelude::*;
+/// # struct MyApp;
+/// # impl<'gl> App<'gl> for MyApp {
+/// #     type Err = Infallible;
+/// #     fn init(ctx: &mut RenderingContext<'gl>) -> Result<Self, Self::Err> { Ok(Self {}) }
+/// #     fn draw(&mut self, ctx: &mut RenderingContext<'gl>, wnd: &Window) {}
+/// # }
+/// fn init<'gl>(ctx: &mut RenderingContext<'gl>) -> Result<MyApp, <MyApp as App<'gl>>::Err> {
+///     MyApp::init(ctx)
+/// }
+///
+/// let mut runner = AppRunner::new(AppOptions::default(), init).unwrap();
+/// while runner.step().is_continue() {
+///     // Handle other event sources
+/// }
+/// ```
+pub struct AppRunner<F>
+where
+    F: for<'gl> InitApp<'gl>,
+{
+    // Fields are dropped in declaration order, i.e. the
+    // `App` and all its OpenGL objects are dropped before
+    // the OpenGL context is destroyed.
+    //
+    // The `'static` lifetime cannot be exploited by the `App`,
+    // as `F` must be able to initialize the `App` for any `'gl`.
+    // The `App` is never exposed, so its OpenGL objects cannot
+    // escape the `AppRunner`.
+    app: <F as InitApp<'static>>::App,
+    ctx: RenderingContext<'static>,
+    events: EventReceiver,
+    frame_events: Vec<WindowEvent>,
+    input: Input,
+    /// Minimum duration of a frame in seconds, from [`AppOptions::max_fps`].
+    min_frame_time: Option<f64>,
+    /// Time the current frame started, from [`Glfw::get_time()`].
+    frame_start: f64,
+    auto_viewport: bool,
+    wnd: Window,
+    glfw: Glfw,
+}
+
+impl<F> AppRunner<F>
+where
+    F: for<'gl> InitApp<'gl>,
+{
+    pub fn new(opts: AppOptions<'_>, f: F) -> Result<Self, AppInitError> {
+        let (glfw, wnd, events) = init(opts.clone(), true)?;
+        // Safety: OpenGL context is current and `RenderingContext` cannot escape the `AppRunner`
+        let mut ctx = unsafe { new_rendering_context(&glfw) };
+        let app = f
+            .init(&mut ctx)
+            .map_err(|err| AppInitError::app(&opts, ctx.gl_version(), err.into()))?;
+
+        Ok(Self {
+            app,
+            ctx,
+            events,
+            frame_events: Vec::new(),
+            input: Input::new(&wnd),
+            min_frame_time: opts
+                .max_fps
+                .filter(|&fps| fps > 0)
+                .map(|fps| 1.0 / fps as f64),
+            frame_start: glfw.get_time(),
+            auto_viewport: opts.auto_viewport,
+            wnd,
+            glfw,
+        })
+    }
+
+    /// Runs exactly one iteration of the main loop, i.e. poll events,
+    /// update, draw, and swap buffers. If [`AppOptions::max_fps`] is set,
+    /// then this also sleeps the remainder of the frame.
+    ///
+    /// Returns [`ControlFlow::Break`] when the window should close,
+    /// in which case the main loop should stop.
+    pub fn step(&mut self) -> ControlFlow<()> {
+        if self.wnd.should_close() {
+            return ControlFlow::Break(());
+        }
 
-        for (_timestamp, evt) in glfw::flush_messages(&events) {
+        self.glfw.poll_events();
+
+        self.frame_events.clear();
+        self.input.begin_frame();
+        for (_timestamp, evt) in glfw::flush_messages(&self.events) {
             match evt {
-                WindowEvent::FramebufferSize(w, h) => unsafe {
-                    gl::Viewport(0, 0, w, h);
-                },
+                WindowEvent::FramebufferSize(w, h) if self.auto_viewport => {
+                    self.ctx.set_viewport((0, 0, w, h));
+                }
                 #[cfg(debug_assertions)]
                 WindowEvent::Key(Key::Escape, _, glfw::Action::Press, _) => {
-                    break 'main;
+                    self.wnd.set_should_close(true);
+                    return ControlFlow::Break(());
                 }
                 WindowEvent::Close => {
-                    break 'main;
+                    self.wnd.set_should_close(true);
+                    return ControlFlow::Break(());
                 }
                 _ => {}
             }
 
-            app.on_event(evt, &mut ctx, &mut wnd);
+            self.input.handle_event(&evt);
+            self.frame_events.push(evt.clone());
+            self.app.on_event(evt, &mut self.ctx, &mut self.wnd);
         }
 
-        app.update(&mut ctx, &mut wnd);
-        app.draw(&mut ctx, &wnd);
+        self.app
+            .on_events(&self.frame_events, &mut self.ctx, &mut self.wnd);
 
-        wnd.swap_buffers();
+        self.app.update(&mut self.ctx, &mut self.wnd, &self.input);
+        self.app.draw(&mut self.ctx, &self.wnd);
+
+        self.wnd.swap_buffers();
 
         #[cfg(debug_assertions)]
         {
@@ -187,26 +660,127 @@ where
             })
             .for_each(|err| eprintln!("gl error: 0x{:04X}", err));
         }
+
+        if let Some(min_frame_time) = self.min_frame_time {
+            let elapsed = self.glfw.get_time() - self.frame_start;
+            if elapsed < min_frame_time {
+                thread::sleep(Duration::from_secs_f64(min_frame_time - elapsed));
+            }
+        }
+        let now = self.glfw.get_time();
+        self.ctx.frame_stats_mut().push(now - self.frame_start);
+        self.frame_start = now;
+
+        ControlFlow::Continue(())
     }
 
-    Ok(())
+    #[inline]
+    pub fn window(&self) -> &Window {
+        &self.wnd
+    }
+
+    #[inline]
+    pub fn window_mut(&mut self) -> &mut Window {
+        &mut self.wnd
+    }
+}
+
+impl<F> Drop for AppRunner<F>
+where
+    F: for<'gl> InitApp<'gl>,
+{
+    fn drop(&mut self) {
+        // The fields are not dropped yet, i.e. the OpenGL context is
+        // still current, while the `App` and its objects still exist
+        if !thread::panicking() {
+            self.app.on_exit(&mut self.ctx);
+        }
+    }
+}
+
+/// Create a `RenderingContext`, which loads extension functions
+/// through `glfw`, see [`RenderingContext::get_proc_address()`].
+///
+/// # Safety
+///
+/// Same as [`RenderingContext::new()`].
+unsafe fn new_rendering_context<'gl>(glfw: &Glfw) -> RenderingContext<'gl> {
+    let mut ctx = RenderingContext::new();
+    let loader = glfw.clone();
+    ctx.set_proc_address_loader(move |symbol| loader.get_proc_address_raw(symbol));
+    ctx
 }
 
-pub fn run_headless_once<F>(f: F)
+pub fn run_headless_once<F>(f: F) -> Result<(), AppInitError>
 where
     F: for<'a> FnOnce(&mut RenderingContext<'a>),
 {
-    run_headless_once_with(AppOptions::default(), f);
+    run_headless_once_with(AppOptions::default(), f)
 }
 
-pub fn run_headless_once_with<F>(opts: AppOptions<'_>, f: F)
+pub fn run_headless_once_with<F>(opts: AppOptions<'_>, f: F) -> Result<(), AppInitError>
 where
     F: for<'a> FnOnce(&mut RenderingContext<'a>),
 {
-    let (_glfw, _wnd, _events) = init(opts, false);
+    let (glfw, _wnd, _events) = init(opts, false)?;
     // Safety: OpenGL context is current and `RenderingContext` cannot escape the closure
-    let mut ctx = unsafe { RenderingContext::new() };
+    let mut ctx = unsafe { new_rendering_context(&glfw) };
     f(&mut ctx);
+    Ok(())
+}
+
+/// Run `f` repeatedly with an invisible window, while still polling
+/// events each iteration, e.g. for long-running offscreen rendering,
+/// which must also process timers or IPC through GLFW events.
+///
+/// Unlike [`run_glfw`], buffers are not swapped, as rendering is
+/// expected to target a [`Framebuffer`](crate::Framebuffer).
+///
+/// Stops when `f` returns [`ControlFlow::Break`] or the window
+/// should close.
+pub fn run_headless<F>(f: F) -> Result<(), AppInitError>
+where
+    F: for<'a> FnMut(
+        &mut RenderingContext<'a>,
+        &mut Glfw,
+        &mut Window,
+        &mut EventReceiver,
+    ) -> ControlFlow<()>,
+{
+    run_headless_with(AppOptions::default(), f)
+}
+
+pub fn run_headless_with<F>(opts: AppOptions<'_>, mut f: F) -> Result<(), AppInitError>
+where
+    F: for<'a> FnMut(
+        &mut RenderingContext<'a>,
+        &mut Glfw,
+        &mut Window,
+        &mut EventReceiver,
+    ) -> ControlFlow<()>,
+{
+    let (mut glfw, mut wnd, mut events) = init(opts, false)?;
+    // Safety: OpenGL context is current and `RenderingContext` cannot escape the closure
+    let mut ctx = unsafe { new_rendering_context(&glfw) };
+
+    while !wnd.should_close() {
+        glfw.poll_events();
+
+        if f(&mut ctx, &mut glfw, &mut wnd, &mut events).is_break() {
+            break;
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            iter::from_fn(|| match unsafe { gl::GetError() } {
+                gl::NO_ERROR => None,
+                err => Some(err),
+            })
+            .for_each(|err| eprintln!("gl error: 0x{:04X}", err));
+        }
+    }
+
+    Ok(())
 }
 
 pub fn run_glfw<F>(f: F) -> Result<(), Box<dyn error::Error>>
@@ -220,7 +794,7 @@ pub fn run_glfw_with<F>(opts: AppOptions<'_>, mut f: F) -> Result<(), Box<dyn er
 where
     F: FnMut(&mut Glfw, &mut Window, &mut EventReceiver),
 {
-    let (mut glfw, mut wnd, mut events) = init(opts, true);
+    let (mut glfw, mut wnd, mut events) = init(opts, true)?;
 
     while !wnd.should_close() {
         glfw.poll_events();
@@ -242,32 +816,117 @@ where
     Ok(())
 }
 
-fn init(opts: AppOptions<'_>, visible: bool) -> (Glfw, Window, EventReceiver) {
+/// Returns the `(x, y, width, height)` work area of the primary monitor,
+/// i.e. the area not occupied by the taskbar or menu bar, e.g. to check
+/// that a restored [`AppOptions::window_pos`] is still on screen.
+///
+/// ```no_run
+/// # use ren::prelude::*;
+/// # fn f(glfw: &mut Glfw) {
+/// let work_area = ren::primary_monitor_work_area(glfw);
+/// # }
+/// ```
+pub fn primary_monitor_work_area(glfw: &mut Glfw) -> Option<(i32, i32, i32, i32)> {
+    glfw.with_primary_monitor(|_, monitor| monitor.map(|monitor| monitor.get_workarea()))
+}
+
+/// Core profile OpenGL versions in descending order,
+/// tried when falling back to [`AppOptions::min_gl_version`].
+const GL_VERSIONS: &[(u32, u32)] = &[
+    (4, 6),
+    (4, 5),
+    (4, 4),
+    (4, 3),
+    (4, 2),
+    (4, 1),
+    (4, 0),
+    (3, 3),
+    (3, 2),
+];
+
+struct GlfwErrors {
+    /// Last error reported while creating the window.
+    last: Option<(glfw::Error, String)>,
+    /// Set after the window was created, subsequent errors
+    /// are then passed to `callback` instead of recorded.
+    window_created: bool,
+    callback: Option<fn(glfw::Error, &str)>,
+}
+
+fn init(
+    opts: AppOptions<'_>,
+    visible: bool,
+) -> Result<(Glfw, Window, EventReceiver), AppInitError> {
+    let errors = Rc::new(RefCell::new(GlfwErrors {
+        last: None,
+        window_created: false,
+        callback: opts.glfw_error_callback,
+    }));
     let mut glfw = glfw::init(Some(glfw::Callback {
-        f: |err, desc, _| panic!("glfw error [{}]: {}", err, desc),
-        data: (),
-    }))
-    .expect("unable to initialize glfw");
-
-    glfw.window_hint(WindowHint::ContextVersion(
-        opts.gl_version.0,
-        opts.gl_version.1,
-    ));
+        f: |err, desc, errors: &Rc<RefCell<GlfwErrors>>| {
+            let mut errors = errors.borrow_mut();
+            if !errors.window_created {
+                errors.last = Some((err, desc));
+            } else if let Some(callback) = errors.callback {
+                drop(errors);
+                callback(err, &desc);
+            }
+        },
+        data: errors.clone(),
+    }))?;
+
     glfw.window_hint(WindowHint::OpenGlProfile(OpenGlProfileHint::Core));
     glfw.window_hint(WindowHint::OpenGlForwardCompat(true));
-    glfw.window_hint(WindowHint::OpenGlDebugContext(
-        opts.gl_debug_output && is_debug_output_supported(opts.gl_version),
-    ));
+    if let Some(behavior) = opts.context_release_behavior {
+        glfw.window_hint(WindowHint::ContextReleaseBehavior(behavior));
+    }
     glfw.window_hint(WindowHint::Visible(false));
+    for hint in &opts.extra_hints {
+        glfw.window_hint(hint.clone());
+    }
+
+    let fallback_versions = GL_VERSIONS.iter().copied().filter(|&version| {
+        (version < opts.gl_version) && opts.min_gl_version.is_some_and(|min| version >= min)
+    });
+    let mut versions = [opts.gl_version]
+        .into_iter()
+        .chain(fallback_versions)
+        .peekable();
+
+    let (gl_version, (mut wnd, events)) = loop {
+        let gl_version = versions.next().unwrap();
 
-    let (mut wnd, events) = glfw
-        .create_window(
+        glfw.window_hint(WindowHint::ContextVersion(gl_version.0, gl_version.1));
+        glfw.window_hint(WindowHint::OpenGlDebugContext(
+            opts.gl_debug_output && is_debug_output_supported(gl_version),
+        ));
+
+        let res = glfw.create_window(
             opts.window_size.0,
             opts.window_size.1,
-            env!("CARGO_PKG_NAME"),
+            opts.title,
             WindowMode::Windowed,
-        )
-        .unwrap();
+        );
+        match (res, errors.borrow_mut().last.take()) {
+            (Some(res), _) => break (gl_version, res),
+            (None, Some((glfw::Error::VersionUnavailable, _))) if versions.peek().is_some() => {}
+            (None, Some((glfw::Error::VersionUnavailable, description))) => {
+                return Err(AppInitError::UnsupportedVersion {
+                    gl_version: opts.gl_version,
+                    min_gl_version: gl_version,
+                    description,
+                });
+            }
+            (None, err) => {
+                return Err(AppInitError::WindowCreation {
+                    title: opts.title.to_owned(),
+                    gl_version,
+                    description: err.map_or_else(|| "unknown error".to_owned(), |(_, desc)| desc),
+                });
+            }
+        }
+    };
+    errors.borrow_mut().window_created = true;
 
     wnd.set_key_polling(true);
     wnd.set_mouse_button_polling(true);
@@ -275,15 +934,41 @@ fn init(opts: AppOptions<'_>, visible: bool) -> (Glfw, Window, EventReceiver) {
     wnd.set_scroll_polling(true);
     wnd.set_framebuffer_size_polling(true);
     wnd.set_close_polling(true);
+    wnd.set_focus_polling(true);
+    wnd.set_iconify_polling(true);
+    wnd.set_sticky_keys(opts.sticky_keys);
 
-    wnd.try_center();
+    if let Some((numer, denom)) = opts.aspect_ratio {
+        wnd.set_aspect_ratio(numer, denom);
+    }
+    if opts.min_window_size.is_some() || opts.max_window_size.is_some() {
+        let (min_w, min_h) = opts.min_window_size.unzip();
+        let (max_w, max_h) = opts.max_window_size.unzip();
+        wnd.set_size_limits(min_w, min_h, max_w, max_h);
+    }
+
+    match opts.window_pos {
+        Some((x, y)) => wnd.set_pos(x, y),
+        None => {
+            wnd.try_center();
+        }
+    }
 
     wnd.make_current();
 
     gl::load_with(|symbol| wnd.get_proc_address(symbol) as *const _);
 
+    let swap_interval = opts.swap_interval.or(opts.vsync.map(|vsync| vsync as i32));
+    if let Some(swap_interval) = swap_interval {
+        // `glfw::SwapInterval::Adaptive` is always `-1`, so the value
+        // is passed directly to preserve intervals below `-1`
+        unsafe {
+            glfw::ffi::glfwSwapInterval(swap_interval);
+        }
+    }
+
     if opts.gl_debug_output {
-        if is_debug_output_supported(opts.gl_version) && init_debug_output() {
+        if is_debug_output_supported(gl_version) && init_debug_output() {
             println!("Enabled OpenGL debug output");
         } else {
             eprintln!("Warning: OpenGL debug output not supported");
@@ -294,5 +979,5 @@ fn init(opts: AppOptions<'_>, visible: bool) -> (Glfw, Window, EventReceiver) {
         wnd.show();
     }
 
-    (glfw, wnd, events)
+    Ok((glfw, wnd, events))
 }
@@ -1,10 +1,25 @@
 // Unsafe code used for OpenGL calls
 #![allow(unsafe_code)]
 
+pub mod prelude {
+    pub use super::DebugSeverity;
+}
+
 use std::ffi::{c_void, CStr};
 use std::ptr;
 use std::slice;
 
+/// Severity of a debug message, see
+/// [`RenderingContext::debug_message()`](crate::RenderingContext::debug_message).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum DebugSeverity {
+    High = gl::DEBUG_SEVERITY_HIGH,
+    Medium = gl::DEBUG_SEVERITY_MEDIUM,
+    Low = gl::DEBUG_SEVERITY_LOW,
+    Notification = gl::DEBUG_SEVERITY_NOTIFICATION,
+}
+
 pub(crate) fn is_debug_output_supported((major, minor): (u32, u32)) -> bool {
     ((major == 4) && (minor >= 3)) || (major > 4)
 }
@@ -1,20 +1,69 @@
 pub mod prelude {
-    pub use super::{VertexArray, VertexArrayDesc};
+    pub use super::{IndexKind, PrimitiveKind, VertexArray, VertexArrayDesc};
 }
 
-use std::fmt;
+use std::ffi::c_void;
+use std::fmt::{self, Write};
 use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
 
 use crate::AttribBinding;
 
-use super::{Attrib, AttribBindPoint, Buffer, GLHandle, RenderingContext};
+use super::{Attrib, AttribBindPoint, Buffer, GLHandle, RawGLHandle, RenderingContext};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum PrimitiveKind {
+    Points = gl::POINTS,
+    Lines = gl::LINES,
+    LineStrip = gl::LINE_STRIP,
+    Triangles = gl::TRIANGLES,
+    TriangleStrip = gl::TRIANGLE_STRIP,
+    TriangleFan = gl::TRIANGLE_FAN,
+}
+
+/// Type of the indices in an element buffer.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum IndexKind {
+    U8 = gl::UNSIGNED_BYTE,
+    U16 = gl::UNSIGNED_SHORT,
+    U32 = gl::UNSIGNED_INT,
+}
+
+impl IndexKind {
+    /// Returns the byte size of a single index.
+    #[inline]
+    pub const fn size(self) -> u32 {
+        match self {
+            Self::U8 => 1,
+            Self::U16 => 2,
+            Self::U32 => 4,
+        }
+    }
+
+    /// Returns the maximum index value, which is conventionally used as
+    /// the [primitive restart](super::RenderingContext::set_primitive_restart) index.
+    #[inline]
+    pub const fn max_index(self) -> u32 {
+        match self {
+            Self::U8 => u8::MAX as u32,
+            Self::U16 => u16::MAX as u32,
+            Self::U32 => u32::MAX,
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct VertexArrayDesc<'gl, 'a> {
     buffers: Vec<&'a Buffer<'gl>>,
     bind_points: Vec<AttribBindPoint>,
     bindings: Vec<AttribBinding>,
+    /// Binding index and divisor of each instanced bind point.
+    binding_divisors: Vec<(u32, u32)>,
     attribs: Vec<Attrib>,
+    element_buffer: Option<(&'a Buffer<'gl>, IndexKind)>,
+    constant_attribs: Vec<(u32, (f32, f32, f32, f32))>,
 }
 
 impl<'gl, 'a> VertexArrayDesc<'gl, 'a> {
@@ -23,7 +72,10 @@ impl<'gl, 'a> VertexArrayDesc<'gl, 'a> {
             buffers: Vec::new(),
             bind_points: Vec::new(),
             bindings: Vec::new(),
+            binding_divisors: Vec::new(),
             attribs: Vec::new(),
+            element_buffer: None,
+            constant_attribs: Vec::new(),
         }
     }
 
@@ -42,11 +94,111 @@ impl<'gl, 'a> VertexArrayDesc<'gl, 'a> {
         self
     }
 
+    /// Advance the attributes sourced from buffer binding `binding_index`
+    /// once every `divisor` instances, instead of once per vertex, e.g.
+    /// a `divisor` of `1` for per-instance transforms. A `divisor` of `0`
+    /// is the default, i.e. per-vertex.
+    pub fn with_binding_divisor(mut self, binding_index: u32, divisor: u32) -> Self {
+        self.binding_divisors.push((binding_index, divisor));
+        self
+    }
+
     pub fn with_attrib(mut self, attrib: Attrib) -> Self {
         self.attribs.push(attrib);
         self
     }
 
+    /// Use `buffer` as the element buffer containing indices of `kind`,
+    /// which is required by [`VertexArray::draw_elements()`].
+    pub fn with_element_buffer(mut self, buffer: &'a Buffer<'gl>, kind: IndexKind) -> Self {
+        self.element_buffer = Some((buffer, kind));
+        self
+    }
+
+    /// Use the constant `value` for attribute `index`, instead of
+    /// sourcing it from a buffer, e.g. a default color. The attribute
+    /// must not also be added using [`with_attrib()`](Self::with_attrib).
+    ///
+    /// Constant attribute values are not part of the vertex array
+    /// state in OpenGL, so they are set when calling
+    /// [`VertexArray::bind()`].
+    pub fn with_constant_attrib(mut self, index: u32, value: (f32, f32, f32, f32)) -> Self {
+        self.constant_attribs.push((index, value));
+        self
+    }
+
+    /// Returns a human-readable description of the resolved layout,
+    /// i.e. for each attribute its format and offset, along with the
+    /// buffer binding and bind point it sources its data from.
+    ///
+    /// ```text
+    /// attrib 0: Float3, offset 0, binding 0 (buffer 1, offset 0, stride 20)
+    /// attrib 1: Float2, offset 12, binding 0 (buffer 1, offset 0, stride 20)
+    /// elements: buffer 2, U16
+    /// ```
+    pub fn describe(&self) -> String {
+        let mut s = String::new();
+
+        for attrib in &self.attribs {
+            // Attributes use the binding index equal to
+            // their attribute index, unless specified
+            let binding_index = self
+                .bindings
+                .iter()
+                .rev()
+                .find(|binding| binding.attrib_index == attrib.index)
+                .map_or(attrib.index, |binding| binding.buffer_binding_index);
+
+            _ = write!(
+                s,
+                "attrib {}: {:?}, offset {}, binding {}",
+                attrib.index, attrib.kind, attrib.offset, binding_index,
+            );
+
+            let bind_point = self
+                .bind_points
+                .iter()
+                .enumerate()
+                .rev()
+                .find(|(_, bind_point)| bind_point.binding_index == binding_index);
+            match bind_point {
+                Some((buffer_index, bind_point)) => {
+                    let buffer = match self.buffers.get(buffer_index) {
+                        Some(buffer) => unsafe { buffer.gl_handle() }.to_string(),
+                        None => "missing".to_owned(),
+                    };
+                    _ = writeln!(
+                        s,
+                        " (buffer {}, offset {}, stride {})",
+                        buffer, bind_point.offset, bind_point.stride,
+                    );
+                }
+                None => {
+                    _ = writeln!(s, " (no bind point)");
+                }
+            }
+        }
+
+        for (index, value) in &self.constant_attribs {
+            _ = writeln!(s, "attrib {}: constant {:?}", index, value);
+        }
+
+        for (binding_index, divisor) in &self.binding_divisors {
+            _ = writeln!(s, "binding {}: divisor {}", binding_index, divisor);
+        }
+
+        if let Some((buffer, kind)) = self.element_buffer {
+            _ = writeln!(
+                s,
+                "elements: buffer {}, {:?}",
+                unsafe { buffer.gl_handle() },
+                kind
+            );
+        }
+
+        s
+    }
+
     pub unsafe fn apply(&self, vao: u32) {
         for (buffer_index, bind_point) in self.bind_points.iter().enumerate() {
             let buffer = &self.buffers[buffer_index];
@@ -57,10 +209,22 @@ impl<'gl, 'a> VertexArrayDesc<'gl, 'a> {
             binding.apply(vao);
         }
 
+        for &(binding_index, divisor) in &self.binding_divisors {
+            gl::VertexArrayBindingDivisor(vao, binding_index, divisor);
+        }
+
         for attrib in &self.attribs {
             attrib.enable(vao);
             attrib.apply(vao);
         }
+
+        for &(index, _) in &self.constant_attribs {
+            gl::DisableVertexArrayAttrib(vao, index);
+        }
+
+        if let Some((buffer, _)) = self.element_buffer {
+            gl::VertexArrayElementBuffer(vao, buffer.gl_handle());
+        }
     }
 }
 
@@ -73,6 +237,13 @@ impl<'gl, 'a> AsRef<VertexArrayDesc<'gl, 'a>> for VertexArrayDesc<'gl, 'a> {
 
 pub struct VertexArray<'gl> {
     handle: u32,
+    index_kind: Option<IndexKind>,
+    /// Handle of the element buffer, if any.
+    element_buffer: Option<u32>,
+    /// Buffer handle of each per-vertex bind point, i.e. without
+    /// a divisor, used for validating draw ranges.
+    vertex_buffers: Vec<(u32, AttribBindPoint)>,
+    constant_attribs: Vec<(u32, (f32, f32, f32, f32))>,
     phantom: PhantomData<&'gl ()>,
 }
 
@@ -87,11 +258,17 @@ impl VertexArray<'static> {
     where
         'gl: 'a,
     {
-        let arr = Self::create();
-        unsafe {
-            desc.as_ref().apply(arr.handle);
-        }
-        arr
+        Self::create_with_desc(desc.as_ref())
+    }
+
+    /// # Safety
+    ///
+    /// Must only be called on a thread where there is a current
+    /// OpenGL context. The returned `VertexArray` must only
+    /// exist, while the OpenGL context is valid.
+    #[inline]
+    pub unsafe fn new_empty_unsafe() -> Self {
+        Self::create()
     }
 }
 
@@ -104,14 +281,42 @@ impl<'gl> VertexArray<'gl> {
     where
         'gl: 'a,
     {
-        let arr = Self::create();
+        Self::create_with_desc(desc.as_ref())
+    }
+
+    /// Create a vertex array without any attributes, e.g. for
+    /// drawing procedural geometry generated from `gl_VertexID`.
+    #[inline]
+    pub fn new_empty(_ctx: &mut RenderingContext<'gl>) -> Self {
+        Self::create()
+    }
+
+    fn create_with_desc(desc: &VertexArrayDesc<'_, '_>) -> Self {
+        let mut arr = Self::create();
+        arr.index_kind = desc.element_buffer.map(|(_, kind)| kind);
+        arr.element_buffer = desc
+            .element_buffer
+            .map(|(buffer, _)| unsafe { buffer.gl_handle() });
+        arr.vertex_buffers = desc
+            .bind_points
+            .iter()
+            .zip(&desc.buffers)
+            .filter(|(bind_point, _)| {
+                !desc
+                    .binding_divisors
+                    .iter()
+                    .any(|&(i, divisor)| i == bind_point.binding_index && divisor != 0)
+            })
+            .map(|(&bind_point, buffer)| (unsafe { buffer.gl_handle() }, bind_point))
+            .collect();
+        arr.constant_attribs = desc.constant_attribs.clone();
         unsafe {
-            desc.as_ref().apply(arr.handle);
+            desc.apply(arr.handle);
         }
         arr
     }
 
-    fn create() -> Self {
+    pub(crate) fn create() -> Self {
         let mut handle = 0;
         unsafe {
             gl::CreateVertexArrays(1, &mut handle);
@@ -119,13 +324,22 @@ impl<'gl> VertexArray<'gl> {
         debug_assert_ne!(handle, 0, "failed creating vertex array");
         Self {
             handle,
+            index_kind: None,
+            element_buffer: None,
+            vertex_buffers: Vec::new(),
+            constant_attribs: Vec::new(),
             phantom: PhantomData,
         }
     }
 
+    /// Bind the vertex array, and set the values of its
+    /// [constant attributes](VertexArrayDesc::with_constant_attrib), if any.
     #[inline]
     pub unsafe fn bind(&self) {
         gl::BindVertexArray(self.handle);
+        for &(index, (x, y, z, w)) in &self.constant_attribs {
+            gl::VertexAttrib4f(index, x, y, z, w);
+        }
     }
 
     #[inline]
@@ -133,15 +347,133 @@ impl<'gl> VertexArray<'gl> {
         self.draw_arrays(gl::TRIANGLES, first * 3, tri_count * 3);
     }
 
+    /// Draw `line_count` lines starting at line `first`.
+    ///
+    /// # Safety
+    ///
+    /// A shader and this vertex array must be bound, and the drawn
+    /// vertices must be within the bounds of the vertex buffers.
+    #[inline]
+    pub unsafe fn draw_lines(&self, first: u32, line_count: u32) {
+        self.draw_arrays(gl::LINES, first * 2, line_count * 2);
+    }
+
     #[inline]
     pub unsafe fn draw_points(&self, first: u32, vertex_count: u32) {
         self.draw_arrays(gl::POINTS, first, vertex_count);
     }
 
     #[inline]
-    unsafe fn draw_arrays(&self, mode: u32, first: u32, vertex_count: u32) {
+    pub(crate) unsafe fn draw_arrays(&self, mode: u32, first: u32, vertex_count: u32) {
         gl::DrawArrays(mode, first as i32, vertex_count as i32);
     }
+
+    /// Draw `index_count` indices starting at index `first`,
+    /// from the element buffer of the vertex array.
+    ///
+    /// # Safety
+    ///
+    /// A shader and this vertex array must be bound, `first..first + index_count`
+    /// must be within the bounds of the element buffer, and all indices must
+    /// be within the bounds of the vertex buffers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex array has no element buffer,
+    /// see [`VertexArrayDesc::with_element_buffer()`].
+    pub unsafe fn draw_elements(&self, mode: PrimitiveKind, first: u32, index_count: u32) {
+        let index_kind = self.index_kind.expect("vertex array has no element buffer");
+        gl::DrawElements(
+            mode as u32,
+            index_count as i32,
+            index_kind as u32,
+            (first * index_kind.size()) as usize as *const c_void,
+        );
+    }
+
+    /// Same as [`draw_elements()`](Self::draw_elements), but additionally
+    /// hints that all indices are within the inclusive range `start..=end`,
+    /// which allows the driver to optimize vertex fetching.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`draw_elements()`](Self::draw_elements), and all indices
+    /// in the drawn range must be within `start..=end`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex array has no element buffer,
+    /// see [`VertexArrayDesc::with_element_buffer()`].
+    pub unsafe fn draw_range_elements(
+        &self,
+        mode: PrimitiveKind,
+        start: u32,
+        end: u32,
+        first: u32,
+        index_count: u32,
+    ) {
+        debug_assert!(start <= end);
+
+        let index_kind = self.index_kind.expect("vertex array has no element buffer");
+        gl::DrawRangeElements(
+            mode as u32,
+            start,
+            end,
+            index_count as i32,
+            index_kind as u32,
+            (first * index_kind.size()) as usize as *const c_void,
+        );
+    }
+
+    /// Returns the number of vertices that can be sourced from all
+    /// per-vertex bind points, based on the current size of their
+    /// buffers, or `None` if the vertex array has no such bind points.
+    pub(crate) fn vertex_capacity(&self) -> Option<u32> {
+        self.vertex_buffers
+            .iter()
+            .map(|&(buffer, bind_point)| {
+                let size = buffer_size(buffer);
+                let offset = bind_point.offset as usize;
+                match bind_point.stride as usize {
+                    _ if size <= offset => 0,
+                    0 => u32::MAX,
+                    stride => ((size - offset) / stride) as u32,
+                }
+            })
+            .min()
+    }
+
+    /// Returns the number of indices in the element buffer,
+    /// based on its current size, if any.
+    pub(crate) fn index_capacity(&self) -> Option<u32> {
+        let (buffer, kind) = self.element_buffer.zip(self.index_kind)?;
+        Some((buffer_size(buffer) / kind.size() as usize) as u32)
+    }
+
+    /// Returns the index kind of the element buffer, if any.
+    #[inline]
+    pub fn index_kind(&self) -> Option<IndexKind> {
+        self.index_kind
+    }
+
+    /// Returns the handle without deleting the vertex array, i.e. the
+    /// caller becomes responsible for calling `glDeleteVertexArrays()`,
+    /// e.g. when handing the vertex array to another library.
+    pub fn into_raw(self) -> RawGLHandle {
+        let mut arr = ManuallyDrop::new(self);
+        // Drop the remaining fields, without deleting the handle
+        drop(mem::take(&mut arr.vertex_buffers));
+        drop(mem::take(&mut arr.constant_attribs));
+        RawGLHandle(arr.handle)
+    }
+}
+
+fn buffer_size(buffer: u32) -> usize {
+    let mut size = 0;
+    unsafe {
+        gl::GetNamedBufferParameteri64v(buffer, gl::BUFFER_SIZE, &mut size);
+    }
+    size as usize
 }
 
 impl GLHandle for VertexArray<'_> {
@@ -1,5 +1,7 @@
 pub mod prelude {
-    pub use super::{Attrib, AttribBindPoint, AttribBinding, AttribFormat, AttribKind};
+    pub use super::{
+        Attrib, AttribBindPoint, AttribBinding, AttribFormat, AttribKind, AttribLayout,
+    };
 }
 
 use std::mem;
@@ -15,7 +17,24 @@ pub enum AttribKind {
 }
 
 impl AttribKind {
-    fn gl_size_type(self) -> (u8, u32) {
+    /// Returns the number of components, e.g. `3` for [`AttribKind::Float3`].
+    #[inline]
+    pub const fn components(self) -> u8 {
+        self.gl_size_type().0
+    }
+
+    /// Returns the byte size of the whole attribute,
+    /// e.g. `12` for [`AttribKind::Float3`].
+    #[inline]
+    pub const fn size_bytes(self) -> u32 {
+        let component_size = match self.gl_size_type().1 {
+            gl::FLOAT => mem::size_of::<f32>() as u32,
+            _ => unreachable!(),
+        };
+        (self.components() as u32) * component_size
+    }
+
+    const fn gl_size_type(self) -> (u8, u32) {
         match self {
             Self::Float1 => (1, gl::FLOAT),
             Self::Float2 => (2, gl::FLOAT),
@@ -49,6 +68,10 @@ impl AttribFormat {
         }
     }
 
+    /// Uses the size of `T` as the offset, i.e. this is
+    /// only correct if `T` is the single preceding field.
+    /// For computing the offsets of multiple interleaved
+    /// attributes use [`AttribLayout`] instead.
     #[inline]
     pub const fn typed_offset<T>(index: u32, kind: AttribKind) -> Self {
         Self::with_offset(index, kind, mem::size_of::<T>() as u32)
@@ -71,6 +94,71 @@ impl AttribFormat {
     }
 }
 
+/// Layout of interleaved attributes, where the offset of
+/// each attribute is computed from the preceding attributes.
+///
+/// ```
+/// # use ren::prelude::*;
+/// let layout = AttribLayout::from_kinds(&[
+///     AttribKind::Float3, // Position
+///     AttribKind::Float2, // Texture coordinates
+///     AttribKind::Float4, // Color
+/// ]);
+/// assert_eq!(layout.attribs()[2].offset, 20);
+/// assert_eq!(layout.stride(), 36);
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Default, Debug)]
+pub struct AttribLayout {
+    attribs: Vec<AttribFormat>,
+    stride: u32,
+}
+
+impl AttribLayout {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            attribs: Vec::new(),
+            stride: 0,
+        }
+    }
+
+    /// Returns a layout, where the attribute indices
+    /// are the indices of `kinds`.
+    pub fn from_kinds(kinds: &[AttribKind]) -> Self {
+        kinds
+            .iter()
+            .enumerate()
+            .fold(Self::new(), |layout, (index, &kind)| {
+                layout.with_attrib(index as u32, kind)
+            })
+    }
+
+    /// Append an attribute, placed after all previous attributes.
+    pub fn with_attrib(mut self, index: u32, kind: AttribKind) -> Self {
+        self.attribs
+            .push(AttribFormat::with_offset(index, kind, self.stride));
+        self.stride += kind.size_bytes();
+        self
+    }
+
+    #[inline]
+    pub fn attribs(&self) -> &[AttribFormat] {
+        &self.attribs
+    }
+
+    #[inline]
+    pub fn into_attribs(self) -> Vec<AttribFormat> {
+        self.attribs
+    }
+
+    /// Returns the distance in bytes between vertices,
+    /// i.e. the total size of all attributes.
+    #[inline]
+    pub fn stride(&self) -> u32 {
+        self.stride
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 pub struct AttribBinding {
     pub attrib_index: u32,
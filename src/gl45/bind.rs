@@ -0,0 +1,137 @@
+pub mod prelude {
+    pub use super::{ShaderBindGuard, TextureBindGuard, VertexArrayBindGuard};
+}
+
+use std::fmt;
+use std::ops::Deref;
+
+use super::{Shader, Texture, VertexArray};
+
+/// Binds a [`VertexArray`] on creation, and unbinds it on drop,
+/// see [`RenderingContext::bind_vertex_array()`](super::RenderingContext::bind_vertex_array).
+pub struct VertexArrayBindGuard<'a, 'gl> {
+    vao: &'a VertexArray<'gl>,
+}
+
+impl<'a, 'gl> VertexArrayBindGuard<'a, 'gl> {
+    #[inline]
+    pub(crate) fn new(vao: &'a VertexArray<'gl>) -> Self {
+        unsafe {
+            vao.bind();
+        }
+        Self { vao }
+    }
+}
+
+impl<'gl> Deref for VertexArrayBindGuard<'_, 'gl> {
+    type Target = VertexArray<'gl>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.vao
+    }
+}
+
+impl Drop for VertexArrayBindGuard<'_, '_> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::BindVertexArray(0);
+        }
+    }
+}
+
+impl fmt::Debug for VertexArrayBindGuard<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VertexArrayBindGuard")
+            .field(self.vao)
+            .finish()
+    }
+}
+
+/// Binds a [`Shader`] on creation, and unbinds it on drop,
+/// see [`RenderingContext::bind_shader()`](super::RenderingContext::bind_shader).
+pub struct ShaderBindGuard<'a, 'gl> {
+    shader: &'a Shader<'gl>,
+}
+
+impl<'a, 'gl> ShaderBindGuard<'a, 'gl> {
+    #[inline]
+    pub(crate) fn new(shader: &'a Shader<'gl>) -> Self {
+        unsafe {
+            shader.bind();
+        }
+        Self { shader }
+    }
+}
+
+impl<'gl> Deref for ShaderBindGuard<'_, 'gl> {
+    type Target = Shader<'gl>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.shader
+    }
+}
+
+impl Drop for ShaderBindGuard<'_, '_> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::UseProgram(0);
+        }
+    }
+}
+
+impl fmt::Debug for ShaderBindGuard<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ShaderBindGuard").field(self.shader).finish()
+    }
+}
+
+/// Binds a [`Texture`] to a texture unit on creation, and unbinds
+/// the texture unit on drop,
+/// see [`RenderingContext::bind_texture()`](super::RenderingContext::bind_texture).
+pub struct TextureBindGuard<'a, 'gl> {
+    texture: &'a Texture<'gl>,
+    unit: u32,
+}
+
+impl<'a, 'gl> TextureBindGuard<'a, 'gl> {
+    #[inline]
+    pub(crate) fn new(texture: &'a Texture<'gl>, unit: u32) -> Self {
+        unsafe {
+            texture.bind(unit);
+        }
+        Self { texture, unit }
+    }
+
+    #[inline]
+    pub fn unit(&self) -> u32 {
+        self.unit
+    }
+}
+
+impl<'gl> Deref for TextureBindGuard<'_, 'gl> {
+    type Target = Texture<'gl>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.texture
+    }
+}
+
+impl Drop for TextureBindGuard<'_, '_> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::BindTextureUnit(self.unit, 0);
+        }
+    }
+}
+
+impl fmt::Debug for TextureBindGuard<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TextureBindGuard")
+            .field(self.texture)
+            .field(&self.unit)
+            .finish()
+    }
+}
@@ -0,0 +1,173 @@
+pub mod prelude {
+    pub use super::TextureHandle;
+}
+
+use std::ffi::c_void;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::sync::OnceLock;
+
+use super::{GLHandle, RenderingContext, SetUniform, Shader, Texture, UniformLocation};
+
+/// Functions of `GL_ARB_bindless_texture`, which are not
+/// included in the core profile bindings.
+struct BindlessFns {
+    get_texture_handle: GetTextureHandle,
+    make_texture_handle_resident: TextureHandleFn,
+    make_texture_handle_non_resident: TextureHandleFn,
+    program_uniform_handle: ProgramUniformHandle,
+}
+
+type GetTextureHandle = unsafe extern "system" fn(u32) -> u64;
+type TextureHandleFn = unsafe extern "system" fn(u64);
+type ProgramUniformHandle = unsafe extern "system" fn(u32, i32, u64);
+
+/// Only set once loaded successfully, such that a context without
+/// the extension does not prevent loading it for a later context.
+static BINDLESS_FNS: OnceLock<BindlessFns> = OnceLock::new();
+
+impl BindlessFns {
+    fn load(ctx: &RenderingContext<'_>) -> Option<Self> {
+        if !ctx.has_extension("GL_ARB_bindless_texture") {
+            return None;
+        }
+
+        let load = |symbol| {
+            let f = ctx.get_proc_address(symbol);
+            (!f.is_null()).then_some(f)
+        };
+
+        let get_texture_handle = load("glGetTextureHandleARB")?;
+        let make_texture_handle_resident = load("glMakeTextureHandleResidentARB")?;
+        let make_texture_handle_non_resident = load("glMakeTextureHandleNonResidentARB")?;
+        let program_uniform_handle = load("glProgramUniformHandleui64ARB")?;
+
+        unsafe {
+            Some(Self {
+                get_texture_handle: mem::transmute::<*const c_void, GetTextureHandle>(
+                    get_texture_handle,
+                ),
+                make_texture_handle_resident: mem::transmute::<*const c_void, TextureHandleFn>(
+                    make_texture_handle_resident,
+                ),
+                make_texture_handle_non_resident: mem::transmute::<*const c_void, TextureHandleFn>(
+                    make_texture_handle_non_resident,
+                ),
+                program_uniform_handle: mem::transmute::<*const c_void, ProgramUniformHandle>(
+                    program_uniform_handle,
+                ),
+            })
+        }
+    }
+
+    fn get() -> &'static Self {
+        match BINDLESS_FNS.get() {
+            Some(fns) => fns,
+            None => panic!(
+                "bindless textures are not supported, see `RenderingContext::is_bindless_texture_supported()`"
+            ),
+        }
+    }
+}
+
+impl RenderingContext<'_> {
+    /// Returns `true` if `GL_ARB_bindless_texture` is supported, in which
+    /// case its functions are loaded. This must be called, before calling
+    /// [`Texture::make_resident()`].
+    pub fn is_bindless_texture_supported(&self) -> bool {
+        if BINDLESS_FNS.get().is_some() {
+            return true;
+        }
+
+        match BindlessFns::load(self) {
+            Some(fns) => {
+                // Another thread may have loaded them in the meantime
+                let _ = BINDLESS_FNS.set(fns);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<'gl> Texture<'gl> {
+    /// Returns a bindless handle of the texture, which is resident until
+    /// the returned `TextureHandle` is dropped. The handle can be set as
+    /// a `sampler2D` uniform declared with `layout(bindless_sampler)`,
+    /// without binding the texture to a texture unit.
+    ///
+    /// The texture cannot be modified while the handle exists, which
+    /// is also a requirement of OpenGL, i.e. the texture is immutable
+    /// after a handle is created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if bindless textures are not supported,
+    /// see [`RenderingContext::is_bindless_texture_supported()`].
+    pub fn make_resident(&self) -> TextureHandle<'_> {
+        let fns = BindlessFns::get();
+        let handle = unsafe {
+            let handle = (fns.get_texture_handle)(self.gl_handle());
+            debug_assert_ne!(handle, 0, "failed getting texture handle");
+            (fns.make_texture_handle_resident)(handle);
+            handle
+        };
+        TextureHandle {
+            handle,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Resident bindless handle of a [`Texture`],
+/// see [`Texture::make_resident()`].
+pub struct TextureHandle<'a> {
+    handle: u64,
+    phantom: PhantomData<&'a Texture<'a>>,
+}
+
+impl TextureHandle<'_> {
+    /// Returns the 64-bit handle, e.g. for storing it in a buffer,
+    /// to be read as a `sampler2D` in a shader.
+    #[inline]
+    pub fn raw(&self) -> u64 {
+        self.handle
+    }
+}
+
+impl Drop for TextureHandle<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            (BindlessFns::get().make_texture_handle_non_resident)(self.handle);
+        }
+    }
+}
+
+impl fmt::Debug for TextureHandle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TextureHandle(0x{:016X})", self.handle)
+    }
+}
+
+/// Set a bindless texture handle, see [`TextureHandle::raw()`].
+///
+/// # Panics
+///
+/// Panics if bindless textures are not supported,
+/// see [`RenderingContext::is_bindless_texture_supported()`].
+impl SetUniform<u64> for Shader<'_> {
+    #[inline]
+    fn set_uniform(&self, loc: UniformLocation, value: u64) {
+        unsafe {
+            (BindlessFns::get().program_uniform_handle)(self.gl_handle(), loc.0 as i32, value);
+        }
+    }
+}
+
+impl SetUniform<&TextureHandle<'_>> for Shader<'_> {
+    #[inline]
+    fn set_uniform(&self, loc: UniformLocation, value: &TextureHandle<'_>) {
+        self.set_uniform(loc, value.raw());
+    }
+}
@@ -1,13 +1,17 @@
 pub mod prelude {
-    pub use super::{Buffer, BufferUsage};
+    pub use super::{Buffer, BufferError, BufferReadback, BufferUsage, BufferWriteMapping};
 }
 
 use std::ffi::c_void;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
+use std::ptr;
+use std::time::Duration;
 
-use super::{GLHandle, RenderingContext};
+use thiserror::Error;
+
+use super::{Fence, GLHandle, RawGLHandle, RenderingContext};
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum BufferUsage {
@@ -32,6 +36,10 @@ impl BufferUsage {
 pub struct Buffer<'gl> {
     handle: u32,
     size: usize,
+    usage: Option<BufferUsage>,
+    /// Whether the handle is deleted on drop,
+    /// see [`Buffer::from_raw_handle()`].
+    owned: bool,
     phantom: PhantomData<&'gl ()>,
 }
 
@@ -81,6 +89,41 @@ impl<'gl> Buffer<'gl> {
         buf
     }
 
+    /// Adopt an existing buffer `handle`, e.g. created by another library.
+    /// The size is queried from the buffer.
+    ///
+    /// If `owned` is `true`, then the buffer is deleted when the returned
+    /// `Buffer` is dropped, i.e. ownership is transferred. Otherwise the
+    /// buffer must be deleted by its creator, after the returned `Buffer`
+    /// is dropped.
+    ///
+    /// The usage of the buffer is unknown, so [`orphan()`](Self::orphan)
+    /// does nothing, until the buffer is written using [`write()`](Self::write).
+    ///
+    /// # Safety
+    ///
+    /// The `handle` must be a valid buffer, created in the same OpenGL
+    /// context as `ctx`, or a shared context. If `owned` is `true`, then
+    /// the buffer must not be deleted by anything else.
+    pub unsafe fn from_raw_handle(
+        _ctx: &mut RenderingContext<'gl>,
+        handle: RawGLHandle,
+        owned: bool,
+    ) -> Self {
+        let handle = handle.gl_handle();
+
+        let mut size = 0;
+        gl::GetNamedBufferParameteri64v(handle, gl::BUFFER_SIZE, &mut size);
+
+        Self {
+            handle,
+            size: size as usize,
+            usage: None,
+            owned,
+            phantom: PhantomData,
+        }
+    }
+
     fn create_multi<const N: usize>() -> [Self; N] {
         let mut handles = [0; N];
         unsafe {
@@ -92,6 +135,8 @@ impl<'gl> Buffer<'gl> {
             Self {
                 handle,
                 size: 0,
+                usage: None,
+                owned: true,
                 phantom: PhantomData,
             }
         })
@@ -99,6 +144,7 @@ impl<'gl> Buffer<'gl> {
 
     pub fn write<T: Copy>(&mut self, usage: BufferUsage, data: &[T]) {
         self.size = data.len() * mem::size_of::<T>();
+        self.usage = Some(usage);
 
         unsafe {
             gl::NamedBufferData(
@@ -110,22 +156,272 @@ impl<'gl> Buffer<'gl> {
         }
     }
 
-    /// Read subset of buffer data into `data` at `offset` bytes.
+    /// Write `data` into the buffer at `offset` bytes, growing the buffer
+    /// if `data` at `offset` is out of bounds.
+    ///
+    /// When growing, the buffer is reallocated with `usage` to fit exactly
+    /// `offset + data`, and the existing data is preserved. The handle
+    /// remains the same, so vertex arrays using the buffer remain valid.
+    /// Otherwise this is the same as [`write_sub()`](Self::write_sub),
+    /// and `usage` is ignored.
+    pub fn write_at<T: Copy>(&mut self, offset: usize, data: &[T], usage: BufferUsage) {
+        let write_end = offset + mem::size_of_val(data);
+        if write_end > self.size {
+            self.grow(write_end, usage);
+        }
+        self.write_sub(offset, data);
+    }
+
+    fn grow(&mut self, new_size: usize, usage: BufferUsage) {
+        let old_size = self.size;
+
+        // Preserve the existing data in a temporary buffer,
+        // as reallocating the data store discards it
+        let tmp = (old_size > 0).then(|| {
+            let [mut tmp] = Self::create_multi();
+            unsafe {
+                tmp.init_storage(old_size, 0);
+                gl::CopyNamedBufferSubData(self.handle, tmp.handle, 0, 0, old_size as isize);
+            }
+            tmp
+        });
+
+        self.size = new_size;
+        self.usage = Some(usage);
+
+        unsafe {
+            gl::NamedBufferData(
+                self.handle,
+                new_size as isize,
+                ptr::null(),
+                usage.gl_draw_usage(),
+            );
+
+            if let Some(tmp) = tmp {
+                gl::CopyNamedBufferSubData(tmp.handle, self.handle, 0, 0, old_size as isize);
+            }
+        }
+    }
+
+    /// Allocate immutable storage of `size` bytes, without any data.
+    ///
+    /// Afterwards the buffer cannot be reallocated, i.e. [`write()`](Self::write)
+    /// and [`orphan()`](Self::orphan) must not be called.
+    pub(crate) unsafe fn init_storage(&mut self, size: usize, flags: u32) {
+        self.size = size;
+        self.usage = None;
+
+        gl::NamedBufferStorage(self.handle, size as isize, ptr::null(), flags);
+    }
+
+    /// Map `len` bytes at `offset` of the buffer's data store.
+    ///
+    /// Returns a null pointer if mapping failed.
+    pub(crate) unsafe fn map_range(&self, offset: usize, len: usize, access: u32) -> *mut c_void {
+        debug_assert!(offset + len <= self.size);
+
+        gl::MapNamedBufferRange(self.handle, offset as isize, len as isize, access)
+    }
+
+    pub(crate) unsafe fn unmap(&self) {
+        gl::UnmapNamedBuffer(self.handle);
+    }
+
+    /// Map `len` bytes at `offset` for writing, invalidating the
+    /// previous contents of the range, i.e. `GL_MAP_WRITE_BIT` and
+    /// `GL_MAP_INVALIDATE_RANGE_BIT`.
+    ///
+    /// As the previous contents are discarded, the driver does not need
+    /// to wait for the GPU or read back the data. This is the recommended
+    /// way to stream data into a subset of a dynamic buffer.
+    ///
+    /// The whole range must be written through the returned mapping,
+    /// as the contents of any unwritten bytes are undefined afterwards.
+    /// The buffer is unmapped when the mapping is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` bytes at `offset` is out of bounds,
+    /// if `len` is zero, or if mapping the buffer failed.
+    pub fn map_write_invalidate(
+        &mut self,
+        offset: usize,
+        len: usize,
+    ) -> BufferWriteMapping<'_, 'gl> {
+        let map_end = offset + len;
+
+        if map_end > self.size {
+            panic!(
+                "index out of bounds: the size is {} but the end index is {}",
+                self.size, map_end
+            );
+        }
+        assert!(len > 0, "mapping an empty range");
+
+        let ptr = unsafe {
+            self.map_range(
+                offset,
+                len,
+                gl::MAP_WRITE_BIT | gl::MAP_INVALIDATE_RANGE_BIT,
+            )
+        };
+        assert!(!ptr.is_null(), "failed mapping buffer");
+
+        BufferWriteMapping {
+            buffer: self,
+            ptr: ptr as *mut u8,
+            len,
+        }
+    }
+
+    /// Write `data` into a subset of the buffer at `offset` bytes.
     ///
     /// # Panics
     ///
     /// Panics if `data` at `offset` is out of bounds.
-    pub fn read<T: Copy>(&self, offset: usize, data: &mut [T]) {
-        let read_size = data.len() * mem::size_of::<T>();
-        let read_end = offset + read_size;
+    pub fn write_sub<T: Copy>(&mut self, offset: usize, data: &[T]) {
+        let write_size = mem::size_of_val(data);
+        let write_end = offset + write_size;
 
-        if read_end > self.size {
+        if write_end > self.size {
             panic!(
                 "index out of bounds: the size is {} but the end index is {}",
-                self.size, read_end
+                self.size, write_end
             );
         }
 
+        unsafe {
+            gl::NamedBufferSubData(
+                self.handle,
+                offset as isize,
+                write_size as isize,
+                data.as_ptr() as *const c_void,
+            );
+        }
+    }
+
+    /// Fill the whole buffer with repeated copies of `value`, e.g. to
+    /// reset counters, without uploading a full vector of `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `T` is not 1, 2, 4, 8, 12, or 16 bytes,
+    /// or if the size of the buffer is not a multiple of it.
+    #[inline]
+    pub fn fill<T: Copy>(&mut self, value: T) {
+        self.fill_range(0, self.size, value);
+    }
+
+    /// Fill `len` bytes at `offset` with repeated copies of `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` bytes at `offset` is out of bounds, if the size of `T`
+    /// is not 1, 2, 4, 8, 12, or 16 bytes, or if `offset` and `len` are not
+    /// multiples of it.
+    pub fn fill_range<T: Copy>(&mut self, offset: usize, len: usize, value: T) {
+        let fill_end = offset + len;
+        if fill_end > self.size {
+            panic!(
+                "index out of bounds: the size is {} but the end index is {}",
+                self.size, fill_end
+            );
+        }
+
+        let value_size = mem::size_of::<T>();
+        // Clear using an unsigned integer format of the same size as `T`,
+        // which copies the bytes of `value` unmodified
+        let (internal_format, format) = match value_size {
+            1 => (gl::R8UI, gl::RED_INTEGER),
+            2 => (gl::R16UI, gl::RED_INTEGER),
+            4 => (gl::R32UI, gl::RED_INTEGER),
+            8 => (gl::RG32UI, gl::RG_INTEGER),
+            12 => (gl::RGB32UI, gl::RGB_INTEGER),
+            16 => (gl::RGBA32UI, gl::RGBA_INTEGER),
+            _ => panic!("cannot fill buffer with a value of {} bytes", value_size),
+        };
+        let ty = match value_size {
+            1 => gl::UNSIGNED_BYTE,
+            2 => gl::UNSIGNED_SHORT,
+            _ => gl::UNSIGNED_INT,
+        };
+        assert_eq!(
+            (offset % value_size, len % value_size),
+            (0, 0),
+            "fill range must be a multiple of {} bytes",
+            value_size
+        );
+
+        unsafe {
+            gl::ClearNamedBufferSubData(
+                self.handle,
+                internal_format,
+                offset as isize,
+                len as isize,
+                format,
+                ty,
+                &value as *const T as *const c_void,
+            );
+        }
+    }
+
+    /// Orphan the buffer's data store, i.e. reallocate it with the
+    /// same size and usage, but without any data.
+    ///
+    /// This allows the driver to hand back fresh memory, instead of
+    /// stalling until the GPU is done using the old data. This is
+    /// the common pattern for streaming data every frame:
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # fn f(buf: &mut Buffer<'_>, vertices: &[[f32; 3]]) {
+    /// buf.orphan();
+    /// buf.write_sub(0, vertices);
+    /// # }
+    /// ```
+    ///
+    /// Does nothing if no data has been written to the buffer.
+    pub fn orphan(&mut self) {
+        let Some(usage) = self.usage else {
+            return;
+        };
+
+        unsafe {
+            gl::NamedBufferData(
+                self.handle,
+                self.size as isize,
+                ptr::null(),
+                usage.gl_draw_usage(),
+            );
+        }
+    }
+
+    /// Read subset of buffer data into `data` at `offset` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` at `offset` is out of bounds,
+    /// see [`try_read()`](Self::try_read).
+    pub fn read<T: Copy>(&self, offset: usize, data: &mut [T]) {
+        if let Err(err) = self.try_read(offset, data) {
+            panic!("{}", err);
+        }
+    }
+
+    /// Same as [`read()`](Self::read), but returns an error
+    /// instead of panicking, if `data` at `offset` is out of bounds.
+    pub fn try_read<T: Copy>(&self, offset: usize, data: &mut [T]) -> Result<(), BufferError> {
+        let read_size = mem::size_of_val(data);
+        let read_end = offset + read_size;
+
+        if read_end > self.size {
+            return Err(BufferError::OutOfBounds(
+                RawGLHandle(self.handle),
+                self.size,
+                read_end,
+            ));
+        }
+
         unsafe {
             gl::GetNamedBufferSubData(
                 self.handle,
@@ -134,6 +430,82 @@ impl<'gl> Buffer<'gl> {
                 data.as_mut_ptr() as *mut c_void,
             );
         }
+
+        Ok(())
+    }
+
+    /// Read the whole buffer into a `Vec`, e.g. to dump its contents
+    /// while debugging.
+    ///
+    /// Returns an error if the size of the buffer is not a multiple
+    /// of the size of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is zero-sized.
+    pub fn read_all<T: Copy>(&self) -> Result<Vec<T>, BufferError> {
+        let elem_size = mem::size_of::<T>();
+        assert_ne!(elem_size, 0, "cannot read zero-sized elements");
+
+        let len = self.size / elem_size;
+        if len * elem_size != self.size {
+            return Err(BufferError::SizeMismatch(
+                RawGLHandle(self.handle),
+                self.size,
+                elem_size,
+            ));
+        }
+
+        let mut data = Vec::<T>::with_capacity(len);
+        unsafe {
+            gl::GetNamedBufferSubData(
+                self.handle,
+                0,
+                self.size as isize,
+                data.as_mut_ptr() as *mut c_void,
+            );
+            data.set_len(len);
+        }
+        Ok(data)
+    }
+
+    /// Asynchronously read `size` bytes of buffer data at `offset` bytes.
+    ///
+    /// Unlike [`read()`](Self::read), this does not stall until the GPU
+    /// is done with the buffer. Instead the data is copied into a staging
+    /// buffer, which can be read from once [`BufferReadback::is_ready()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` bytes at `offset` is out of bounds.
+    pub fn read_async(&self, offset: usize, size: usize) -> BufferReadback<'gl> {
+        let read_end = offset + size;
+
+        if read_end > self.size {
+            panic!(
+                "index out of bounds: the size is {} but the end index is {}",
+                self.size, read_end
+            );
+        }
+
+        let [mut staging] = Self::create_multi();
+        if size > 0 {
+            unsafe {
+                staging.init_storage(size, 0);
+                gl::CopyNamedBufferSubData(
+                    self.handle,
+                    staging.handle,
+                    offset as isize,
+                    0,
+                    size as isize,
+                );
+            }
+        }
+
+        BufferReadback {
+            staging,
+            fence: Fence::create(),
+        }
     }
 
     /// Returns the byte size of the buffer's data.
@@ -150,6 +522,144 @@ impl<'gl> Buffer<'gl> {
         }
         size as u32
     }
+
+    /// Returns the handle without deleting the buffer, i.e. the
+    /// caller becomes responsible for calling `glDeleteBuffers()`,
+    /// e.g. when handing the buffer to another library.
+    #[inline]
+    pub fn into_raw(self) -> RawGLHandle {
+        let handle = RawGLHandle(self.handle);
+        mem::forget(self);
+        handle
+    }
+}
+
+/// Write-only mapping of a range of a buffer,
+/// see [`Buffer::map_write_invalidate()`].
+///
+/// The buffer is unmapped when `BufferWriteMapping` is dropped.
+pub struct BufferWriteMapping<'a, 'gl> {
+    buffer: &'a mut Buffer<'gl>,
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl BufferWriteMapping<'_, '_> {
+    /// Write `data` into the mapped range at `offset` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` at `offset` is out of bounds.
+    pub fn write<T: Copy>(&mut self, offset: usize, data: &[T]) {
+        let write_size = mem::size_of_val(data);
+        let write_end = offset + write_size;
+
+        if write_end > self.len {
+            panic!(
+                "index out of bounds: the size is {} but the end index is {}",
+                self.len, write_end
+            );
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr() as *const u8, self.ptr.add(offset), write_size);
+        }
+    }
+
+    /// Returns a pointer to the start of the mapped range.
+    ///
+    /// The mapped memory must only be written to, and
+    /// must not be accessed after the mapping is dropped.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.ptr
+    }
+
+    /// Returns the byte size of the mapped range.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Drop for BufferWriteMapping<'_, '_> {
+    fn drop(&mut self) {
+        unsafe {
+            self.buffer.unmap();
+        }
+    }
+}
+
+impl fmt::Debug for BufferWriteMapping<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BufferWriteMapping({}, {})",
+            self.buffer.handle, self.len
+        )
+    }
+}
+
+/// Pending asynchronous read of buffer data,
+/// see [`Buffer::read_async()`].
+pub struct BufferReadback<'gl> {
+    staging: Buffer<'gl>,
+    fence: Fence<'gl>,
+}
+
+impl<'gl> BufferReadback<'gl> {
+    /// Returns `true` if the data can be read without blocking.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.fence.signaled()
+    }
+
+    /// Block until the data is ready or `timeout` expires.
+    ///
+    /// Returns `true` if the data is ready.
+    #[inline]
+    pub fn wait(&self, timeout: Duration) -> bool {
+        self.fence.wait(timeout)
+    }
+
+    /// Read subset of the read back data into `data` at `offset` bytes.
+    ///
+    /// Blocks if the data is not [ready](Self::is_ready) yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` at `offset` is out of bounds.
+    #[inline]
+    pub fn read<T: Copy>(&self, offset: usize, data: &mut [T]) {
+        self.staging.read(offset, data);
+    }
+
+    /// Returns the byte size of the read back data.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.staging.size()
+    }
+
+    #[inline]
+    pub fn fence(&self) -> &Fence<'gl> {
+        &self.fence
+    }
+}
+
+impl fmt::Debug for BufferReadback<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "BufferReadback({}, {})",
+            self.staging.handle,
+            self.size()
+        )
+    }
 }
 
 impl GLHandle for Buffer<'_> {
@@ -161,8 +671,10 @@ impl GLHandle for Buffer<'_> {
 
 impl Drop for Buffer<'_> {
     fn drop(&mut self) {
-        unsafe {
-            gl::DeleteBuffers(1, &self.handle);
+        if self.owned {
+            unsafe {
+                gl::DeleteBuffers(1, &self.handle);
+            }
         }
     }
 }
@@ -172,3 +684,11 @@ impl fmt::Debug for Buffer<'_> {
         write!(f, "Buffer({})", self.handle)
     }
 }
+
+#[derive(Error, Debug)]
+pub enum BufferError {
+    #[error("index out of bounds: the size is {1} but the end index is {2}")]
+    OutOfBounds(RawGLHandle, usize, usize),
+    #[error("buffer [{0}] size {1} is not a multiple of the element size {2}")]
+    SizeMismatch(RawGLHandle, usize, usize),
+}
@@ -0,0 +1,188 @@
+pub mod prelude {
+    pub use super::{DebugDraw, DebugDrawError};
+}
+
+use std::fmt;
+
+use thiserror::Error;
+
+use super::{
+    AttribBindPoint, AttribBinding, AttribKind, AttribLayout, Buffer, BufferUsage,
+    RenderingContext, SetUniform, Shader, ShaderError, ShaderStage, ShaderStageError,
+    UniformLocation, VertexArray, VertexArrayDesc,
+};
+
+const VERTEX_SHADER: &str = r#"#version 450 core
+
+layout(location = 0) uniform mat4 view_proj;
+
+layout(location = 0) in vec3 in_pos;
+layout(location = 1) in vec4 in_color;
+
+out vec4 v_color;
+
+void main() {
+    gl_Position = view_proj * vec4(in_pos, 1.0);
+    v_color = in_color;
+}
+"#;
+
+const FRAGMENT_SHADER: &str = r#"#version 450 core
+
+in vec4 v_color;
+
+out vec4 out_color;
+
+void main() {
+    out_color = v_color;
+}
+"#;
+
+const VIEW_PROJ_LOCATION: UniformLocation = UniformLocation(0);
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct DebugVertex {
+    pos: [f32; 3],
+    color: [f32; 4],
+}
+
+impl DebugVertex {
+    #[inline]
+    fn new((x, y, z): (f32, f32, f32), (r, g, b, a): (f32, f32, f32, f32)) -> Self {
+        Self {
+            pos: [x, y, z],
+            color: [r, g, b, a],
+        }
+    }
+}
+
+/// Immediate mode renderer of lines and points, e.g. for
+/// visualizing normals, bounding boxes, and paths.
+///
+/// ```no_run
+/// # use ren::prelude::*;
+/// # fn f(ctx: &mut RenderingContext<'_>, view_proj: [f32; 16]) -> Result<(), DebugDrawError> {
+/// let mut debug = DebugDraw::new(ctx)?;
+///
+/// debug.line((0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 0.0, 0.0, 1.0));
+/// debug.point((0.0, 1.0, 0.0), (0.0, 1.0, 0.0, 1.0));
+/// debug.flush(ctx, &view_proj);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The width of lines and size of points can be changed using
+/// [`RenderingContext::set_line_width()`] and
+/// [`RenderingContext::set_point_size()`].
+pub struct DebugDraw<'gl> {
+    shader: Shader<'gl>,
+    vao: VertexArray<'gl>,
+    buffer: Buffer<'gl>,
+    lines: Vec<DebugVertex>,
+    points: Vec<DebugVertex>,
+}
+
+impl<'gl> DebugDraw<'gl> {
+    pub fn new(ctx: &mut RenderingContext<'gl>) -> Result<Self, DebugDrawError> {
+        let shader = {
+            let vs = ShaderStage::new_vertex(ctx, VERTEX_SHADER)?;
+            let fs = ShaderStage::new_fragment(ctx, FRAGMENT_SHADER)?;
+            Shader::new(ctx, &[vs, fs])?
+        };
+
+        let buffer = Buffer::new(ctx);
+
+        let layout = AttribLayout::from_kinds(&[
+            AttribKind::Float3, // Position
+            AttribKind::Float4, // Color
+        ]);
+        let mut desc = VertexArrayDesc::new()
+            .with_buffer(&buffer)
+            .with_bind_point(AttribBindPoint::new(0, 0, layout.stride()));
+        for &attrib in layout.attribs() {
+            desc = desc
+                .with_binding(AttribBinding::new(attrib.index, 0))
+                .with_attrib(attrib);
+        }
+        let vao = VertexArray::new(ctx, desc);
+
+        Ok(Self {
+            shader,
+            vao,
+            buffer,
+            lines: Vec::new(),
+            points: Vec::new(),
+        })
+    }
+
+    /// Submit a line segment from `a` to `b`.
+    #[inline]
+    pub fn line(&mut self, a: (f32, f32, f32), b: (f32, f32, f32), color: (f32, f32, f32, f32)) {
+        self.lines.push(DebugVertex::new(a, color));
+        self.lines.push(DebugVertex::new(b, color));
+    }
+
+    #[inline]
+    pub fn point(&mut self, p: (f32, f32, f32), color: (f32, f32, f32, f32)) {
+        self.points.push(DebugVertex::new(p, color));
+    }
+
+    /// Draw and clear all submitted lines and points, transformed
+    /// by the column-major `view_proj` matrix.
+    ///
+    /// This binds the vertex array and shader.
+    pub fn flush(&mut self, ctx: &mut RenderingContext<'gl>, view_proj: &[f32; 16]) {
+        if self.lines.is_empty() && self.points.is_empty() {
+            return;
+        }
+
+        let line_vertex_count = self.lines.len() as u32;
+        let point_count = self.points.len() as u32;
+
+        // Lines are followed by points in the same buffer
+        self.lines.append(&mut self.points);
+        self.buffer.write(BufferUsage::Stream, &self.lines);
+        self.lines.clear();
+
+        self.shader.set_uniform(VIEW_PROJ_LOCATION, view_proj);
+
+        let vao = ctx.bind_vertex_array(&self.vao);
+        let _shader = ctx.bind_shader(&self.shader);
+        unsafe {
+            if line_vertex_count > 0 {
+                vao.draw_lines(0, line_vertex_count / 2);
+            }
+            if point_count > 0 {
+                vao.draw_points(line_vertex_count, point_count);
+            }
+        }
+    }
+
+    /// Clear all submitted lines and points, without drawing them.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.points.clear();
+    }
+}
+
+impl fmt::Debug for DebugDraw<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugDraw")
+            .field("shader", &self.shader)
+            .field("vao", &self.vao)
+            .field("buffer", &self.buffer)
+            .field("lines", &(self.lines.len() / 2))
+            .field("points", &self.points.len())
+            .finish()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DebugDrawError {
+    #[error("creating debug draw shader stage failed: {0}")]
+    ShaderStage(#[from] ShaderStageError),
+    #[error("creating debug draw shader failed: {0}")]
+    Shader(#[from] ShaderError),
+}
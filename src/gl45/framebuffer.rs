@@ -0,0 +1,413 @@
+pub mod prelude {
+    pub use super::{BlitMask, Framebuffer, FramebufferAttachment, FramebufferError};
+}
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::BitOr;
+
+use thiserror::Error;
+
+use super::{
+    GLHandle, MultisampleTexture, RawGLHandle, Renderbuffer, RenderingContext, Texture,
+    TextureFilter,
+};
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum FramebufferAttachment {
+    /// Color attachment `i`, i.e. `GL_COLOR_ATTACHMENTi`.
+    Color(u32),
+    Depth,
+    Stencil,
+    DepthStencil,
+}
+
+impl FramebufferAttachment {
+    const fn gl_attachment(self) -> u32 {
+        match self {
+            Self::Color(i) => gl::COLOR_ATTACHMENT0 + i,
+            Self::Depth => gl::DEPTH_ATTACHMENT,
+            Self::Stencil => gl::STENCIL_ATTACHMENT,
+            Self::DepthStencil => gl::DEPTH_STENCIL_ATTACHMENT,
+        }
+    }
+}
+
+/// Buffers to copy when blitting, which can be combined, e.g.
+/// <code>[BlitMask::COLOR] | [BlitMask::DEPTH]</code>.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct BlitMask(u32);
+
+impl BlitMask {
+    pub const COLOR: Self = Self(gl::COLOR_BUFFER_BIT);
+    pub const DEPTH: Self = Self(gl::DEPTH_BUFFER_BIT);
+    pub const STENCIL: Self = Self(gl::STENCIL_BUFFER_BIT);
+}
+
+impl BitOr for BlitMask {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+pub struct Framebuffer<'gl> {
+    handle: u32,
+    /// Indices of attached color attachments.
+    color_attachments: Vec<u32>,
+    /// Indices of color attachments set as draw buffers.
+    draw_buffers: Vec<u32>,
+    phantom: PhantomData<&'gl ()>,
+}
+
+impl Framebuffer<'static> {
+    /// # Safety
+    ///
+    /// Must only be called on a thread where there is a current
+    /// OpenGL context. The returned `Framebuffer` must only
+    /// exist, while the OpenGL context is valid.
+    #[inline]
+    pub unsafe fn new_unsafe() -> Self {
+        Self::create()
+    }
+}
+
+impl<'gl> Framebuffer<'gl> {
+    #[inline]
+    pub fn new(_ctx: &mut RenderingContext<'gl>) -> Self {
+        Self::create()
+    }
+
+    /// Create a framebuffer with `depth` as its only attachment,
+    /// and no color buffers, e.g. for rendering a shadow map.
+    ///
+    /// The shadow map can then be sampled as a `sampler2DShadow`
+    /// with hardware PCF, see [`Texture::set_compare()`].
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # fn f(ctx: &mut RenderingContext<'_>) -> Result<(), FramebufferError> {
+    /// let mut shadow_map = ctx.create_texture((2048, 2048), InternalFormat::Depth24);
+    /// shadow_map.set_filter(TextureFilter::Linear);
+    /// shadow_map.set_compare(Some(DepthFunc::LessEqual));
+    ///
+    /// let fb = Framebuffer::new_depth_only(ctx, &shadow_map);
+    /// fb.check_status()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_depth_only(ctx: &mut RenderingContext<'gl>, depth: &Texture<'gl>) -> Self {
+        let mut fb = Self::new(ctx);
+        fb.attach_texture(FramebufferAttachment::Depth, depth);
+        fb.disable_color_buffers();
+        fb
+    }
+
+    fn create() -> Self {
+        let mut handle = 0;
+        unsafe {
+            gl::CreateFramebuffers(1, &mut handle);
+        }
+        debug_assert_ne!(handle, 0, "failed creating framebuffer");
+        Self {
+            handle,
+            color_attachments: Vec::new(),
+            // Initially only `GL_COLOR_ATTACHMENT0` is a draw buffer
+            draw_buffers: vec![0],
+            phantom: PhantomData,
+        }
+    }
+
+    /// Attach mip level 0 of `texture` to `attachment`.
+    #[inline]
+    pub fn attach_texture(&mut self, attachment: FramebufferAttachment, texture: &Texture<'gl>) {
+        self.attach_texture_level(attachment, texture, 0);
+    }
+
+    /// Attach mip `level` of `texture` to `attachment`, e.g. to generate
+    /// a mip chain by rendering each level from the previous level.
+    ///
+    /// The viewport must be set to the size of the level, see
+    /// [`Texture::level_size()`].
+    pub fn attach_texture_level(
+        &mut self,
+        attachment: FramebufferAttachment,
+        texture: &Texture<'gl>,
+        level: u32,
+    ) {
+        debug_assert!(level < texture.levels());
+
+        self.track_attachment(attachment);
+        unsafe {
+            gl::NamedFramebufferTexture(
+                self.handle,
+                attachment.gl_attachment(),
+                texture.gl_handle(),
+                level as i32,
+            );
+        }
+    }
+
+    /// Attach `layer` of mip `level` of `texture` to `attachment`,
+    /// e.g. a single face of a cube map, where the faces are the
+    /// layers `0..6` in the order `+X, -X, +Y, -Y, +Z, -Z`.
+    ///
+    /// # Safety
+    ///
+    /// The `texture` must be an array, cube map, or 3D texture, e.g.
+    /// created by another library, with at least `level + 1` mip levels
+    /// and `layer + 1` layers. The `texture` must not be deleted while
+    /// it is attached.
+    pub unsafe fn attach_texture_layer(
+        &mut self,
+        attachment: FramebufferAttachment,
+        texture: &impl GLHandle,
+        level: u32,
+        layer: u32,
+    ) {
+        self.track_attachment(attachment);
+        gl::NamedFramebufferTextureLayer(
+            self.handle,
+            attachment.gl_attachment(),
+            texture.gl_handle(),
+            level as i32,
+            layer as i32,
+        );
+    }
+
+    #[inline]
+    pub fn attach_multisample_texture(
+        &mut self,
+        attachment: FramebufferAttachment,
+        texture: &MultisampleTexture<'gl>,
+    ) {
+        self.track_attachment(attachment);
+        unsafe {
+            gl::NamedFramebufferTexture(
+                self.handle,
+                attachment.gl_attachment(),
+                texture.gl_handle(),
+                0,
+            );
+        }
+    }
+
+    #[inline]
+    pub fn attach_renderbuffer(
+        &mut self,
+        attachment: FramebufferAttachment,
+        renderbuffer: &Renderbuffer<'gl>,
+    ) {
+        self.track_attachment(attachment);
+        unsafe {
+            gl::NamedFramebufferRenderbuffer(
+                self.handle,
+                attachment.gl_attachment(),
+                gl::RENDERBUFFER,
+                renderbuffer.gl_handle(),
+            );
+        }
+    }
+
+    /// Attach each of `textures` to color attachment `0..n`,
+    /// and set them as the draw buffers, e.g. for a G-buffer.
+    pub fn attach_color_textures(&mut self, textures: &[&Texture<'gl>]) {
+        for (i, texture) in textures.iter().enumerate() {
+            self.attach_texture(FramebufferAttachment::Color(i as u32), texture);
+        }
+
+        let draw_buffers = (0..textures.len() as u32).collect::<Vec<_>>();
+        self.set_draw_buffers(&draw_buffers);
+    }
+
+    /// Set the color attachments, that fragment shader outputs `0..n`
+    /// are written to, i.e. output `i` is written to color attachment
+    /// `color_attachments[i]`.
+    pub fn set_draw_buffers(&mut self, color_attachments: &[u32]) {
+        let bufs = color_attachments
+            .iter()
+            .map(|&i| FramebufferAttachment::Color(i).gl_attachment())
+            .collect::<Vec<_>>();
+        unsafe {
+            gl::NamedFramebufferDrawBuffers(self.handle, bufs.len() as i32, bufs.as_ptr());
+        }
+
+        self.draw_buffers = color_attachments.to_vec();
+    }
+
+    /// Disable drawing to and reading from color buffers, i.e.
+    /// set both the draw buffer and read buffer to `GL_NONE`,
+    /// e.g. for a framebuffer with only a depth attachment.
+    pub fn disable_color_buffers(&mut self) {
+        unsafe {
+            gl::NamedFramebufferDrawBuffer(self.handle, gl::NONE);
+            gl::NamedFramebufferReadBuffer(self.handle, gl::NONE);
+        }
+
+        self.draw_buffers.clear();
+    }
+
+    /// Clear draw buffer `draw_buffer` to `color`, i.e. the color
+    /// attachment `color_attachments[draw_buffer]` as given to
+    /// [`set_draw_buffers()`](Self::set_draw_buffers), e.g. to clear
+    /// each G-buffer attachment to a different value.
+    ///
+    /// This is not affected by the clear color of the [`RenderingContext`],
+    /// but the color mask and scissor test do apply.
+    pub fn clear_color_attachment(&mut self, draw_buffer: u32, color: [f32; 4]) {
+        debug_assert!((draw_buffer as usize) < self.draw_buffers.len());
+        unsafe {
+            gl::ClearNamedFramebufferfv(self.handle, gl::COLOR, draw_buffer as i32, color.as_ptr());
+        }
+    }
+
+    /// Clear the depth and stencil attachments to `depth` and `stencil`.
+    ///
+    /// This is not affected by the clear values of the [`RenderingContext`],
+    /// but the depth and stencil masks and the scissor test do apply.
+    pub fn clear_depth_stencil(&mut self, depth: f32, stencil: i32) {
+        unsafe {
+            gl::ClearNamedFramebufferfi(self.handle, gl::DEPTH_STENCIL, 0, depth, stencil);
+        }
+    }
+
+    fn track_attachment(&mut self, attachment: FramebufferAttachment) {
+        if let FramebufferAttachment::Color(i) = attachment {
+            if !self.color_attachments.contains(&i) {
+                self.color_attachments.push(i);
+            }
+        }
+    }
+
+    /// Returns `Ok` if the framebuffer is complete,
+    /// i.e. it can be rendered to.
+    ///
+    /// Additionally, this checks that all draw buffers have an
+    /// attachment, as otherwise writes to them are silently discarded.
+    pub fn check_status(&self) -> Result<(), FramebufferError> {
+        if !self.color_attachments.is_empty() {
+            let missing = self
+                .draw_buffers
+                .iter()
+                .find(|i| !self.color_attachments.contains(i));
+            if let Some(&i) = missing {
+                return Err(FramebufferError::MissingDrawBuffer(
+                    RawGLHandle(self.handle),
+                    i,
+                ));
+            }
+        }
+
+        let status = unsafe { gl::CheckNamedFramebufferStatus(self.handle, gl::FRAMEBUFFER) };
+        if status == gl::FRAMEBUFFER_COMPLETE {
+            Ok(())
+        } else {
+            Err(FramebufferError::Incomplete(
+                RawGLHandle(self.handle),
+                status_name(status),
+            ))
+        }
+    }
+
+    /// Bind the framebuffer for both drawing and reading.
+    ///
+    /// # Safety
+    ///
+    /// Another framebuffer must be bound, e.g. using [`bind_default()`],
+    /// before this framebuffer is dropped.
+    ///
+    /// [`bind_default()`]: Self::bind_default
+    #[inline]
+    pub unsafe fn bind(&self) {
+        gl::BindFramebuffer(gl::FRAMEBUFFER, self.handle);
+    }
+
+    /// Bind the default framebuffer, i.e. the window.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called on a thread where there is a current
+    /// OpenGL context.
+    #[inline]
+    pub unsafe fn bind_default() {
+        gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+    }
+
+    /// Copy the `src` rectangle of this framebuffer to the `dst` rectangle
+    /// of the default framebuffer, e.g. to resolve an MSAA render target.
+    ///
+    /// Both rectangles are `(x0, y0, x1, y1)`, where `(x0, y0)` is inclusive and
+    /// `(x1, y1)` is exclusive. If the sizes differ, the copy is scaled using
+    /// `filter`, which must be [`TextureFilter::Nearest`] unless `mask` is
+    /// only [`BlitMask::COLOR`]. When resolving multisampled attachments the
+    /// rectangles must be the same size.
+    pub fn blit_to_default(
+        &self,
+        src: (i32, i32, i32, i32),
+        dst: (i32, i32, i32, i32),
+        mask: BlitMask,
+        filter: TextureFilter,
+    ) {
+        unsafe {
+            gl::BlitNamedFramebuffer(
+                self.handle,
+                0,
+                src.0,
+                src.1,
+                src.2,
+                src.3,
+                dst.0,
+                dst.1,
+                dst.2,
+                dst.3,
+                mask.0,
+                filter as u32,
+            );
+        }
+    }
+}
+
+impl GLHandle for Framebuffer<'_> {
+    #[inline]
+    unsafe fn gl_handle(&self) -> u32 {
+        self.handle
+    }
+}
+
+impl Drop for Framebuffer<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteFramebuffers(1, &self.handle);
+        }
+    }
+}
+
+impl fmt::Debug for Framebuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Framebuffer({})", self.handle)
+    }
+}
+
+const fn status_name(status: u32) -> &'static str {
+    match status {
+        gl::FRAMEBUFFER_UNDEFINED => "undefined",
+        gl::FRAMEBUFFER_INCOMPLETE_ATTACHMENT => "incomplete attachment",
+        gl::FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT => "missing attachment",
+        gl::FRAMEBUFFER_INCOMPLETE_DRAW_BUFFER => "incomplete draw buffer",
+        gl::FRAMEBUFFER_INCOMPLETE_READ_BUFFER => "incomplete read buffer",
+        gl::FRAMEBUFFER_UNSUPPORTED => "unsupported",
+        gl::FRAMEBUFFER_INCOMPLETE_MULTISAMPLE => "incomplete multisample",
+        gl::FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS => "incomplete layer targets",
+        _ => "unknown",
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum FramebufferError {
+    #[error("framebuffer [{0}] is incomplete: {1}")]
+    Incomplete(RawGLHandle, &'static str),
+    #[error("framebuffer [{0}] has no color attachment {1} for its draw buffer")]
+    MissingDrawBuffer(RawGLHandle, u32),
+}
@@ -0,0 +1,167 @@
+pub mod prelude {
+    pub use super::{LoadedResource, ResourceLoader};
+}
+
+use std::fmt;
+use std::mem;
+use std::sync::Mutex;
+
+use super::{Buffer, BufferUsage, InternalFormat, PixelFormat, RenderingContext, Texture};
+
+/// Queue of resource data staged from any thread, e.g. assets decoded by
+/// worker threads, which are turned into OpenGL objects on the thread of
+/// the OpenGL context by calling [`process()`](Self::process).
+///
+/// Each resource is identified by a user-defined `key`, e.g. an asset path.
+///
+/// ```no_run
+/// # use std::collections::HashMap;
+/// # use std::sync::Arc;
+/// # use std::thread;
+/// # use ren::prelude::*;
+/// # fn decode_png(path: &str) -> ((u32, u32), Vec<u8>) { unimplemented!() }
+/// # struct MyApp<'gl> {
+/// #     textures: HashMap<&'static str, Texture<'gl>>,
+/// #     buffers: HashMap<&'static str, Buffer<'gl>>,
+/// # }
+/// # impl<'gl> MyApp<'gl> {
+/// # fn f(&mut self, ctx: &mut RenderingContext<'gl>) {
+/// let loader = Arc::new(ResourceLoader::new());
+///
+/// let worker = Arc::clone(&loader);
+/// thread::spawn(move || {
+///     let (size, pixels) = decode_png("grass.png");
+///     worker.load_texture("grass", size, InternalFormat::Rgba8, PixelFormat::Rgba, pixels);
+/// });
+///
+/// // In `update()`
+/// for res in loader.process(ctx) {
+///     match res {
+///         LoadedResource::Texture(key, texture) => {
+///             self.textures.insert(key, texture);
+///         }
+///         LoadedResource::Buffer(key, buffer) => {
+///             self.buffers.insert(key, buffer);
+///         }
+///     }
+/// }
+/// # }
+/// # }
+/// ```
+pub struct ResourceLoader<K> {
+    pending: Mutex<Vec<PendingResource<K>>>,
+}
+
+enum PendingResource<K> {
+    Texture {
+        key: K,
+        size: (u32, u32),
+        internal_format: InternalFormat,
+        format: PixelFormat,
+        pixels: Vec<u8>,
+    },
+    Buffer {
+        key: K,
+        usage: BufferUsage,
+        data: Vec<u8>,
+    },
+}
+
+impl<K> ResourceLoader<K> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Stage `pixels` to be uploaded to a new texture of `size`.
+    pub fn load_texture(
+        &self,
+        key: K,
+        size: (u32, u32),
+        internal_format: InternalFormat,
+        format: PixelFormat,
+        pixels: Vec<u8>,
+    ) {
+        self.push(PendingResource::Texture {
+            key,
+            size,
+            internal_format,
+            format,
+            pixels,
+        });
+    }
+
+    /// Stage `data` to be written to a new buffer, e.g. vertices
+    /// converted to bytes.
+    pub fn load_buffer(&self, key: K, usage: BufferUsage, data: Vec<u8>) {
+        self.push(PendingResource::Buffer { key, usage, data });
+    }
+
+    fn push(&self, res: PendingResource<K>) {
+        self.pending
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(res);
+    }
+
+    /// Returns the number of staged resources not yet processed.
+    pub fn pending(&self) -> usize {
+        self.pending
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .len()
+    }
+
+    /// Create OpenGL objects for all resources staged so far,
+    /// in the order they were staged.
+    pub fn process<'gl>(&self, ctx: &mut RenderingContext<'gl>) -> Vec<LoadedResource<'gl, K>> {
+        // Take the staged resources first, so worker
+        // threads are not blocked while uploading
+        let pending = mem::take(&mut *self.pending.lock().unwrap_or_else(|err| err.into_inner()));
+
+        pending
+            .into_iter()
+            .map(|res| match res {
+                PendingResource::Texture {
+                    key,
+                    size,
+                    internal_format,
+                    format,
+                    pixels,
+                } => {
+                    let mut texture = Texture::new(ctx, size, internal_format);
+                    texture.upload_image_data(size, format, pixels);
+                    LoadedResource::Texture(key, texture)
+                }
+                PendingResource::Buffer { key, usage, data } => {
+                    LoadedResource::Buffer(key, Buffer::with_data(ctx, usage, &data))
+                }
+            })
+            .collect()
+    }
+}
+
+impl<K> Default for ResourceLoader<K> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> fmt::Debug for ResourceLoader<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceLoader")
+            .field("pending", &self.pending())
+            .finish()
+    }
+}
+
+/// OpenGL object created by [`ResourceLoader::process()`],
+/// along with the key it was staged with.
+#[derive(Debug)]
+pub enum LoadedResource<'gl, K> {
+    Texture(K, Texture<'gl>),
+    Buffer(K, Buffer<'gl>),
+}
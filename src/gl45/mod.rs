@@ -4,8 +4,21 @@
 pub mod prelude {
     pub use super::array::prelude::*;
     pub use super::attrib::prelude::*;
+    pub use super::bind::prelude::*;
+    #[cfg(feature = "bindless")]
+    pub use super::bindless::prelude::*;
     pub use super::buffer::prelude::*;
+    pub use super::debug_draw::prelude::*;
+    pub use super::framebuffer::prelude::*;
+    pub use super::loader::prelude::*;
+    pub use super::query::prelude::*;
+    pub use super::renderbuffer::prelude::*;
+    pub use super::ring_buffer::prelude::*;
     pub use super::shader::prelude::*;
+    pub use super::sprite_batch::prelude::*;
+    pub use super::state::prelude::*;
+    pub use super::std140::prelude::*;
+    pub use super::sync::prelude::*;
     pub use super::texture::prelude::*;
     pub use super::uniform::prelude::*;
 
@@ -14,20 +27,55 @@ pub mod prelude {
 
 mod array;
 mod attrib;
+mod bind;
+#[cfg(feature = "bindless")]
+mod bindless;
 mod buffer;
+mod debug_draw;
+mod framebuffer;
+mod loader;
+mod preprocess;
+#[cfg(feature = "glam")]
+mod projection;
+mod query;
+mod renderbuffer;
+mod ring_buffer;
 mod shader;
+mod sprite_batch;
+mod state;
+mod std140;
+mod sync;
 mod texture;
 mod uniform;
 
 pub use self::array::*;
 pub use self::attrib::*;
+pub use self::bind::*;
+#[cfg(feature = "bindless")]
+pub use self::bindless::*;
 pub use self::buffer::*;
+pub use self::debug_draw::*;
+pub use self::framebuffer::*;
+pub use self::loader::*;
+pub use self::query::*;
+pub use self::renderbuffer::*;
+pub use self::ring_buffer::*;
 pub use self::shader::*;
+pub use self::sprite_batch::*;
+pub use self::state::{BlendFactor, ClipDepthMode, ClipOrigin, CullFace, DepthFunc, FrontFace};
+pub use self::std140::*;
+pub use self::sync::*;
 pub use self::texture::*;
 pub use self::uniform::*;
 
+use std::ffi::{c_char, c_void, CStr};
 use std::fmt;
 use std::marker::PhantomData;
+use std::ptr;
+
+use crate::{DebugSeverity, FrameStats};
+
+use self::state::StateCache;
 
 pub trait GLHandle {
     unsafe fn gl_handle(&self) -> u32;
@@ -52,6 +100,16 @@ pub trait GLHandle {
 #[repr(transparent)]
 pub struct RawGLHandle(pub(crate) u32);
 
+impl RawGLHandle {
+    /// Wrap an OpenGL handle, e.g. received from another library,
+    /// to adopt it using `from_raw_handle()`, e.g.
+    /// [`Texture::from_raw_handle()`].
+    #[inline]
+    pub const fn new(handle: u32) -> Self {
+        Self(handle)
+    }
+}
+
 impl GLHandle for RawGLHandle {
     #[inline]
     unsafe fn gl_handle(&self) -> u32 {
@@ -66,7 +124,23 @@ impl fmt::Display for RawGLHandle {
     }
 }
 
+type ProcAddressLoader = Box<dyn Fn(&str) -> *const c_void>;
+
 pub struct RenderingContext<'gl> {
+    clear_color: (f32, f32, f32, f32),
+    clear_depth: f32,
+    clear_stencil: i32,
+    state: StateCache,
+    warning_callback: Box<dyn FnMut(&str)>,
+    glsl_version: Option<String>,
+    proc_address_loader: Option<ProcAddressLoader>,
+    /// Lazily created by [`draw_fullscreen_triangle()`](Self::draw_fullscreen_triangle).
+    fullscreen_vao: Option<VertexArray<'gl>>,
+    /// Whether each texture unit is allocated, see
+    /// [`allocate_texture_unit()`](Self::allocate_texture_unit).
+    texture_units: Vec<bool>,
+    /// Updated by the main loop, see [`frame_stats()`](Self::frame_stats).
+    frame_stats: FrameStats,
     phantom: PhantomData<&'gl ()>,
 }
 
@@ -80,24 +154,802 @@ impl<'gl> RenderingContext<'gl> {
         self::texture::init();
 
         Self {
+            clear_color: (0.0, 0.0, 0.0, 0.0),
+            clear_depth: 1.0,
+            clear_stencil: 0,
+            state: StateCache::default(),
+            warning_callback: Box::new(print_warning),
+            glsl_version: None,
+            proc_address_loader: None,
+            fullscreen_vao: None,
+            texture_units: Vec::new(),
+            frame_stats: FrameStats::default(),
             phantom: PhantomData,
         }
     }
 
+    /// Set the callback receiving warnings, e.g. the info log of
+    /// shaders which compiled or linked successfully, but with warnings.
+    ///
+    /// By default warnings are printed to stderr.
+    #[inline]
+    pub fn set_warning_callback(&mut self, f: impl FnMut(&str) + 'static) {
+        self.warning_callback = Box::new(f);
+    }
+
+    #[inline]
+    pub(crate) fn warning_callback(&mut self) -> &mut dyn FnMut(&str) {
+        &mut self.warning_callback
+    }
+
+    /// Set the GLSL version, e.g. `"450 core"`, which is automatically
+    /// prepended as `#version 450 core` to shader stage sources, that
+    /// do not already start with a `#version` directive.
+    ///
+    /// By default no version is prepended.
+    #[inline]
+    pub fn set_glsl_version(&mut self, version: Option<&str>) {
+        self.glsl_version = version.map(str::to_owned);
+    }
+
+    #[inline]
+    pub fn glsl_version(&self) -> Option<&str> {
+        self.glsl_version.as_deref()
+    }
+
+    /// Set the function used by [`get_proc_address()`](Self::get_proc_address),
+    /// e.g. `|symbol| wnd.get_proc_address(symbol)` for a GLFW window.
+    ///
+    /// This is set automatically when running an [`App`](crate::App).
+    #[inline]
+    pub fn set_proc_address_loader(&mut self, f: impl Fn(&str) -> *const c_void + 'static) {
+        self.proc_address_loader = Some(Box::new(f));
+    }
+
+    /// Returns the address of the OpenGL function `symbol` for
+    /// the current context, or null if no loader is set, see
+    /// [`set_proc_address_loader()`](Self::set_proc_address_loader).
+    ///
+    /// This allows sharing the OpenGL context with other OpenGL
+    /// libraries, e.g. UI renderers like `egui_glow`:
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # mod glow {
+    /// #     pub struct Context;
+    /// #     impl Context {
+    /// #         pub unsafe fn from_loader_function(f: impl FnMut(&str) -> *const std::ffi::c_void) -> Self { Self }
+    /// #     }
+    /// # }
+    /// # fn f(ctx: &RenderingContext<'_>) {
+    /// let gl = unsafe { glow::Context::from_loader_function(|symbol| ctx.get_proc_address(symbol)) };
+    /// # }
+    /// ```
+    ///
+    /// Note that state changed by other libraries is not tracked,
+    /// see [`invalidate_state_cache()`](Self::invalidate_state_cache).
+    #[inline]
+    pub fn get_proc_address(&self, symbol: &str) -> *const c_void {
+        match &self.proc_address_loader {
+            Some(loader) => loader(symbol),
+            None => ptr::null(),
+        }
+    }
+
+    /// Returns the `(major, minor)` version of the current OpenGL context,
+    /// e.g. the version obtained when falling back to a lower version,
+    /// see [`AppOptions::min_gl_version`](crate::AppOptions::min_gl_version).
+    pub fn gl_version(&self) -> (u32, u32) {
+        let (mut major, mut minor) = (0, 0);
+        unsafe {
+            gl::GetIntegerv(gl::MAJOR_VERSION, &mut major);
+            gl::GetIntegerv(gl::MINOR_VERSION, &mut minor);
+        }
+        (major as u32, minor as u32)
+    }
+
+    /// Returns the maximum number of samples supported for multisampling,
+    /// i.e. the largest valid `samples` for multisampled textures and
+    /// renderbuffers, and for `WindowHint::Samples`.
+    pub fn max_samples(&self) -> u32 {
+        let mut samples = 0;
+        unsafe {
+            gl::GetIntegerv(gl::MAX_SAMPLES, &mut samples);
+        }
+        samples as u32
+    }
+
+    /// Returns `true` if the OpenGL extension `name`
+    /// is supported, e.g. `"GL_ARB_bindless_texture"`.
+    pub fn has_extension(&self, name: &str) -> bool {
+        let mut count = 0;
+        unsafe {
+            gl::GetIntegerv(gl::NUM_EXTENSIONS, &mut count);
+        }
+
+        (0..count as u32).any(|i| {
+            let ext = unsafe { gl::GetStringi(gl::EXTENSIONS, i) };
+            !ext.is_null()
+                && unsafe { CStr::from_ptr(ext as *const c_char) }.to_bytes() == name.as_bytes()
+        })
+    }
+
+    /// Enable or disable printing OpenGL debug messages at runtime,
+    /// e.g. to toggle verbose diagnostics while reproducing a bug.
+    ///
+    /// Returns `true` if debug output is enabled. Enabling it requires
+    /// OpenGL 4.3 and a debug context, i.e. the app must have been
+    /// started with [`AppOptions::gl_debug_output`](crate::AppOptions::gl_debug_output)
+    /// or <code>[WindowHint::OpenGlDebugContext]\(true)</code>.
+    ///
+    /// [WindowHint::OpenGlDebugContext]: crate::WindowHint::OpenGlDebugContext
+    pub fn set_debug_output(&mut self, enabled: bool) -> bool {
+        if enabled {
+            crate::debug_output::init_debug_output()
+        } else {
+            unsafe {
+                gl::Disable(gl::DEBUG_OUTPUT);
+            }
+            false
+        }
+    }
+
+    /// Returns the frame times of the most recent frames, when running
+    /// an [`App`](crate::App), e.g. for displaying an FPS overlay.
+    #[inline]
+    pub fn frame_stats(&self) -> &FrameStats {
+        &self.frame_stats
+    }
+
+    #[inline]
+    pub(crate) fn frame_stats_mut(&mut self) -> &mut FrameStats {
+        &mut self.frame_stats
+    }
+
+    /// Insert an application message into the debug output, e.g. to
+    /// correlate app events with OpenGL errors, or as a marker in GPU
+    /// captures. The message is only reported if debug output is
+    /// enabled, see [`set_debug_output()`](Self::set_debug_output).
+    pub fn debug_message(&mut self, severity: DebugSeverity, message: &str) {
+        unsafe {
+            gl::DebugMessageInsert(
+                gl::DEBUG_SOURCE_APPLICATION,
+                gl::DEBUG_TYPE_MARKER,
+                0,
+                severity as u32,
+                message.len() as i32,
+                message.as_ptr() as *const c_char,
+            );
+        }
+    }
+
+    /// Forget all cached state, such that the next state setter
+    /// always issues its OpenGL call.
+    ///
+    /// `RenderingContext` skips redundant state changes, e.g. calling
+    /// [`set_clear_color()`](Self::set_clear_color) with the same color
+    /// every frame. This must be called after changing the same state
+    /// through raw OpenGL calls, otherwise the cache is out of sync.
+    #[inline]
+    pub fn invalidate_state_cache(&mut self) {
+        self.state = StateCache::default();
+    }
+
+    /// Call `f` and afterwards restore the bound program and vertex
+    /// array, whether blending and the depth test are enabled, and the
+    /// viewport, e.g. around external OpenGL code that leaves state dirty.
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # struct Ui;
+    /// # impl Ui { unsafe fn draw(&mut self) {} }
+    /// # fn f(ctx: &mut RenderingContext<'_>, ui: &mut Ui) {
+    /// ctx.scoped(|ctx| unsafe { ui.draw() });
+    /// # }
+    /// ```
+    ///
+    /// The state cache is invalidated afterwards, as `f` may have
+    /// changed other state through raw OpenGL calls.
+    pub fn scoped<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let (mut program, mut vao) = (0, 0);
+        let mut viewport = [0; 4];
+        let (blend, depth_test);
+        unsafe {
+            gl::GetIntegerv(gl::CURRENT_PROGRAM, &mut program);
+            gl::GetIntegerv(gl::VERTEX_ARRAY_BINDING, &mut vao);
+            gl::GetIntegerv(gl::VIEWPORT, viewport.as_mut_ptr());
+            blend = gl::IsEnabled(gl::BLEND) == gl::TRUE;
+            depth_test = gl::IsEnabled(gl::DEPTH_TEST) == gl::TRUE;
+        }
+
+        let ret = f(self);
+
+        unsafe {
+            gl::UseProgram(program as u32);
+            gl::BindVertexArray(vao as u32);
+            gl::Viewport(viewport[0], viewport[1], viewport[2], viewport[3]);
+            set_capability(gl::BLEND, blend);
+            set_capability(gl::DEPTH_TEST, depth_test);
+        }
+        self.invalidate_state_cache();
+
+        ret
+    }
+
+    #[inline]
+    pub fn set_clear_color(&mut self, color: (f32, f32, f32, f32)) {
+        self.clear_color = color;
+        self.apply_clear_color();
+    }
+
+    /// Returns the color last set by [`set_clear_color()`](Self::set_clear_color).
+    #[inline]
+    pub fn clear_color(&self) -> (f32, f32, f32, f32) {
+        self.clear_color
+    }
+
+    #[inline]
+    fn apply_clear_color(&mut self) {
+        if StateCache::update(&mut self.state.clear_color, self.clear_color) {
+            let (r, g, b, a) = self.clear_color;
+            unsafe {
+                gl::ClearColor(r, g, b, a);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn set_clear_depth(&mut self, depth: f32) {
+        self.clear_depth = depth;
+        self.apply_clear_depth();
+    }
+
+    /// Returns the depth last set by [`set_clear_depth()`](Self::set_clear_depth),
+    /// which defaults to `1.0`.
+    #[inline]
+    pub fn clear_depth(&self) -> f32 {
+        self.clear_depth
+    }
+
+    #[inline]
+    fn apply_clear_depth(&mut self) {
+        if StateCache::update(&mut self.state.clear_depth, self.clear_depth) {
+            unsafe {
+                gl::ClearDepth(self.clear_depth as f64);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn set_clear_stencil(&mut self, stencil: i32) {
+        self.clear_stencil = stencil;
+        self.apply_clear_stencil();
+    }
+
+    /// Returns the value last set by [`set_clear_stencil()`](Self::set_clear_stencil),
+    /// which defaults to `0`.
+    #[inline]
+    pub fn clear_stencil(&self) -> i32 {
+        self.clear_stencil
+    }
+
+    #[inline]
+    fn apply_clear_stencil(&mut self) {
+        if StateCache::update(&mut self.state.clear_stencil, self.clear_stencil) {
+            unsafe {
+                gl::ClearStencil(self.clear_stencil);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn set_depth_test(&mut self, enabled: bool) {
+        if StateCache::update(&mut self.state.depth_test, enabled) {
+            unsafe {
+                set_capability(gl::DEPTH_TEST, enabled);
+            }
+        }
+    }
+
+    /// Enable blending with the `(src, dst)` factors, or disable
+    /// blending if `None`, e.g. `Some((SrcAlpha, OneMinusSrcAlpha))`
+    /// for conventional alpha blending.
+    #[inline]
+    pub fn set_blend_func(&mut self, func: Option<(BlendFactor, BlendFactor)>) {
+        if StateCache::update(&mut self.state.blend_func, func) {
+            unsafe {
+                set_capability(gl::BLEND, func.is_some());
+                if let Some((src, dst)) = func {
+                    gl::BlendFunc(src as u32, dst as u32);
+                }
+            }
+        }
+    }
+
+    /// Enable offsetting the depth of filled polygons by `(factor, units)`,
+    /// or disable it if `None`, e.g. `Some((-1.0, -1.0))` to pull decals
+    /// towards the camera to avoid z-fighting with coplanar geometry.
+    #[inline]
+    pub fn set_polygon_offset(&mut self, offset: Option<(f32, f32)>) {
+        if StateCache::update(&mut self.state.polygon_offset, offset) {
+            unsafe {
+                set_capability(gl::POLYGON_OFFSET_FILL, offset.is_some());
+                if let Some((factor, units)) = offset {
+                    gl::PolygonOffset(factor, units);
+                }
+            }
+        }
+    }
+
+    /// Enable or disable deriving the MSAA sample coverage from the
+    /// alpha of the fragment, e.g. for alpha-tested foliage and
+    /// particles, which then blend without sorting.
+    #[inline]
+    pub fn set_alpha_to_coverage(&mut self, enabled: bool) {
+        if StateCache::update(&mut self.state.alpha_to_coverage, enabled) {
+            unsafe {
+                set_capability(gl::SAMPLE_ALPHA_TO_COVERAGE, enabled);
+            }
+        }
+    }
+
+    /// Enable per-sample shading, where at least `min_fraction` of the
+    /// samples of each pixel are shaded individually, or disable it if
+    /// `None`, e.g. `Some(1.0)` to shade every sample of an MSAA target
+    /// for alpha-tested foliage.
+    #[inline]
+    pub fn set_min_sample_shading(&mut self, min_fraction: Option<f32>) {
+        if StateCache::update(&mut self.state.min_sample_shading, min_fraction) {
+            unsafe {
+                set_capability(gl::SAMPLE_SHADING, min_fraction.is_some());
+                if let Some(min_fraction) = min_fraction {
+                    gl::MinSampleShading(min_fraction);
+                }
+            }
+        }
+    }
+
+    /// Set the width in pixels of rasterized lines, which defaults to `1.0`.
+    ///
+    /// Wide lines are deprecated in forward compatible contexts,
+    /// i.e. widths other than `1.0` may not be supported.
+    #[inline]
+    pub fn set_line_width(&mut self, width: f32) {
+        if StateCache::update(&mut self.state.line_width, width) {
+            unsafe {
+                gl::LineWidth(width);
+            }
+        }
+    }
+
+    /// Set the size in pixels of rasterized points, which defaults to `1.0`,
+    /// unless the vertex shader writes `gl_PointSize`.
+    #[inline]
+    pub fn set_point_size(&mut self, size: f32) {
+        if StateCache::update(&mut self.state.point_size, size) {
+            unsafe {
+                gl::PointSize(size);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn set_depth_func(&mut self, func: DepthFunc) {
+        if StateCache::update(&mut self.state.depth_func, func) {
+            unsafe {
+                gl::DepthFunc(func as u32);
+            }
+        }
+    }
+
+    /// Set the window coordinate origin and the clip space depth range,
+    /// i.e. `set_clip_control(ClipOrigin::LowerLeft, ClipDepthMode::ZeroToOne)`
+    /// matches Direct3D style projection matrices, e.g. for reverse-Z.
+    #[inline]
+    pub fn set_clip_control(&mut self, origin: ClipOrigin, depth_mode: ClipDepthMode) {
+        if StateCache::update(&mut self.state.clip_control, (origin, depth_mode)) {
+            unsafe {
+                gl::ClipControl(origin as u32, depth_mode as u32);
+            }
+        }
+    }
+
+    /// Configure reverse-Z, i.e. a `[0, 1]` clip space depth range,
+    /// a [`Greater`](DepthFunc::Greater) depth test, and clearing depth
+    /// to `0.0`, which greatly improves depth precision.
+    ///
+    /// Projection matrices must be built accordingly, i.e. mapping
+    /// the near plane to `1.0` and the far plane to `0.0`, which
+    /// `RenderingContext::perspective()` does when `glam` is enabled.
+    pub fn enable_reverse_z(&mut self) {
+        self.set_clip_control(ClipOrigin::LowerLeft, ClipDepthMode::ZeroToOne);
+        self.set_depth_func(DepthFunc::Greater);
+        self.set_clear_depth(0.0);
+    }
+
+    /// Set the `(x, y, width, height)` viewport, i.e. viewport `0`.
+    #[inline]
+    pub fn set_viewport(&mut self, (x, y, w, h): (i32, i32, i32, i32)) {
+        unsafe {
+            gl::Viewport(x, y, w, h);
+        }
+    }
+
+    /// Set the `(x, y, width, height)` viewports `0..n`, e.g. for
+    /// split-screen rendering in a single pass, where a geometry
+    /// shader selects the viewport by writing `gl_ViewportIndex`.
+    pub fn set_viewports(&mut self, viewports: &[(f32, f32, f32, f32)]) {
+        let v = viewports
+            .iter()
+            .flat_map(|&(x, y, w, h)| [x, y, w, h])
+            .collect::<Vec<_>>();
+        unsafe {
+            gl::ViewportArrayv(0, viewports.len() as i32, v.as_ptr());
+        }
+    }
+
+    #[inline]
+    pub fn set_scissor_test(&mut self, enabled: bool) {
+        if StateCache::update(&mut self.state.scissor_test, enabled) {
+            unsafe {
+                set_capability(gl::SCISSOR_TEST, enabled);
+            }
+        }
+    }
+
+    /// Set the `(x, y, width, height)` scissor box, i.e. scissor box `0`,
+    /// which only has an effect if the scissor test is enabled, see
+    /// [`RenderingContext::set_scissor_test()`].
+    #[inline]
+    pub fn set_scissor(&mut self, (x, y, w, h): (i32, i32, i32, i32)) {
+        unsafe {
+            gl::Scissor(x, y, w, h);
+        }
+    }
+
+    /// Set the `(x, y, width, height)` scissor boxes `0..n`, one for
+    /// each viewport set by [`RenderingContext::set_viewports()`].
+    pub fn set_scissors(&mut self, scissors: &[(i32, i32, i32, i32)]) {
+        let v = scissors
+            .iter()
+            .flat_map(|&(x, y, w, h)| [x, y, w, h])
+            .collect::<Vec<_>>();
+        unsafe {
+            gl::ScissorArrayv(0, scissors.len() as i32, v.as_ptr());
+        }
+    }
+
+    /// Set the winding order of front-facing polygons, which defaults
+    /// to [`CounterClockwise`](FrontFace::CounterClockwise), e.g. set
+    /// [`Clockwise`](FrontFace::Clockwise) for meshes imported with
+    /// clockwise winding, when face culling is enabled.
+    #[inline]
+    pub fn set_front_face(&mut self, front_face: FrontFace) {
+        if StateCache::update(&mut self.state.front_face, front_face) {
+            unsafe {
+                gl::FrontFace(front_face as u32);
+            }
+        }
+    }
+
+    /// Enable culling of `face` polygons, or disable face culling
+    /// if `None`, e.g. `Some(CullFace::Back)` for closed meshes.
+    #[inline]
+    pub fn set_cull_face(&mut self, face: Option<CullFace>) {
+        if StateCache::update(&mut self.state.cull_face, face) {
+            unsafe {
+                set_capability(gl::CULL_FACE, face.is_some());
+                if let Some(face) = face {
+                    gl::CullFace(face as u32);
+                }
+            }
+        }
+    }
+
+    /// Enable or disable writing each of the `(r, g, b, a)`
+    /// color components to the color buffer.
+    #[inline]
+    pub fn set_color_mask(&mut self, mask: (bool, bool, bool, bool)) {
+        if StateCache::update(&mut self.state.color_mask, mask) {
+            let (r, g, b, a) = mask;
+            unsafe {
+                gl::ColorMask(r as u8, g as u8, b as u8, a as u8);
+            }
+        }
+    }
+
+    /// Enable or disable writing to the depth buffer.
+    #[inline]
+    pub fn set_depth_mask(&mut self, enabled: bool) {
+        if StateCache::update(&mut self.state.depth_mask, enabled) {
+            unsafe {
+                gl::DepthMask(enabled as u8);
+            }
+        }
+    }
+
+    /// Enable primitive restart using `index` as the restart index,
+    /// or disable primitive restart if `None`.
+    ///
+    /// When drawing elements, encountering the restart index starts a
+    /// new primitive, e.g. allowing many triangle strips to be drawn in
+    /// a single draw call. The restart index is compared against the
+    /// index values as is, so conventionally the maximum value of the
+    /// [`IndexKind`] is used, i.e. `0xFFFF` for `u16` and `0xFFFFFFFF`
+    /// for `u32` indices, see [`IndexKind::max_index()`].
+    #[inline]
+    pub fn set_primitive_restart(&mut self, index: Option<u32>) {
+        if StateCache::update(&mut self.state.primitive_restart, index) {
+            unsafe {
+                set_capability(gl::PRIMITIVE_RESTART, index.is_some());
+                if let Some(index) = index {
+                    gl::PrimitiveRestartIndex(index);
+                }
+            }
+        }
+    }
+
+    /// Bind `vao` until the returned guard is dropped.
+    #[inline]
+    pub fn bind_vertex_array<'a>(
+        &mut self,
+        vao: &'a VertexArray<'gl>,
+    ) -> VertexArrayBindGuard<'a, 'gl> {
+        VertexArrayBindGuard::new(vao)
+    }
+
+    /// Bind `shader` until the returned guard is dropped.
+    #[inline]
+    pub fn bind_shader<'a>(&mut self, shader: &'a Shader<'gl>) -> ShaderBindGuard<'a, 'gl> {
+        ShaderBindGuard::new(shader)
+    }
+
+    /// Bind `texture` to the texture `unit`, until the returned guard is dropped.
+    #[inline]
+    pub fn bind_texture<'a>(
+        &mut self,
+        texture: &'a Texture<'gl>,
+        unit: u32,
+    ) -> TextureBindGuard<'a, 'gl> {
+        TextureBindGuard::new(texture, unit)
+    }
+
+    /// Returns the lowest texture unit, which is not currently allocated,
+    /// and marks it as allocated until it is freed using
+    /// [`free_texture_unit()`](Self::free_texture_unit), to avoid
+    /// textures unintentionally sharing a unit.
+    ///
+    /// Returns `None` if all `GL_MAX_COMBINED_TEXTURE_IMAGE_UNITS`
+    /// texture units are allocated.
+    ///
+    /// Only units handed out by this method are tracked, i.e. units
+    /// used directly, e.g. `layout(binding = 0)`, are not reserved.
+    pub fn allocate_texture_unit(&mut self) -> Option<TextureUnit> {
+        if self.texture_units.is_empty() {
+            let mut max_units = 0;
+            unsafe {
+                gl::GetIntegerv(gl::MAX_COMBINED_TEXTURE_IMAGE_UNITS, &mut max_units);
+            }
+            self.texture_units = vec![false; max_units.max(0) as usize];
+        }
+
+        let index = self.texture_units.iter().position(|&used| !used)?;
+        self.texture_units[index] = true;
+        Some(TextureUnit::new(index as u32))
+    }
+
+    /// Free a texture unit allocated by
+    /// [`allocate_texture_unit()`](Self::allocate_texture_unit),
+    /// so it can be handed out again.
+    #[inline]
+    pub fn free_texture_unit(&mut self, unit: TextureUnit) {
+        let used = self.texture_units.get_mut(unit.index() as usize);
+        debug_assert!(
+            used.as_deref() == Some(&true),
+            "freeing texture unit {} which is not allocated",
+            unit.index()
+        );
+        if let Some(used) = used {
+            *used = false;
+        }
+    }
+
+    /// Discard all subsequent draw commands, until
+    /// [`end_conditional_render()`](Self::end_conditional_render) is called,
+    /// if `query` results in zero samples passing, e.g. an occlusion query
+    /// of a bounding box.
     #[inline]
-    pub fn set_clear_color(&mut self, (r, g, b, a): (f32, f32, f32, f32)) {
+    pub fn begin_conditional_render(&mut self, query: &Query<'gl>, mode: ConditionalRenderMode) {
         unsafe {
-            gl::ClearColor(r, g, b, a);
+            gl::BeginConditionalRender(query.gl_handle(), mode as u32);
         }
     }
 
+    #[inline]
+    pub fn end_conditional_render(&mut self) {
+        unsafe {
+            gl::EndConditionalRender();
+        }
+    }
+
+    /// Clear the color buffer using [`clear_color()`](Self::clear_color),
+    /// even if the OpenGL clear color was changed in the meantime, e.g.
+    /// by a render pass followed by [`invalidate_state_cache()`](Self::invalidate_state_cache).
     #[inline]
     pub fn clear_color_buffer(&mut self) {
+        self.apply_clear_color();
         unsafe {
             gl::Clear(gl::COLOR_BUFFER_BIT);
         }
     }
 
+    /// Draw a single triangle covering the whole viewport, using a vertex
+    /// array without attributes, e.g. for post-processing passes.
+    ///
+    /// The bound vertex shader must generate the vertices from `gl_VertexID`:
+    ///
+    /// ```glsl
+    /// out vec2 v_uv;
+    ///
+    /// void main() {
+    ///     v_uv = vec2((gl_VertexID << 1) & 2, gl_VertexID & 2);
+    ///     gl_Position = vec4(v_uv * 2.0 - 1.0, 0.0, 1.0);
+    /// }
+    /// ```
+    pub fn draw_fullscreen_triangle(&mut self) {
+        let vao = self.fullscreen_vao.get_or_insert_with(VertexArray::create);
+        let vao = VertexArrayBindGuard::new(vao);
+        unsafe {
+            vao.draw_triangles(0, 1);
+        }
+    }
+
+    /// Draw `count` vertices starting at vertex `first` of `vao` as
+    /// `mode` primitives using `shader`, i.e. without having to bind
+    /// either manually. Both are unbound again afterwards.
+    ///
+    /// If `vao` has an element buffer, then `count` indices starting at
+    /// index `first` are drawn instead, see [`VertexArray::draw_elements()`].
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # fn f<'gl>(ctx: &mut RenderingContext<'gl>, shader: &Shader<'gl>, vao: &VertexArray<'gl>) {
+    /// ctx.draw(shader, vao, PrimitiveKind::Triangles, 0, 3);
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `first..first + count` is out of bounds
+    /// of the element buffer, or of the buffers of the per-vertex bind
+    /// points when drawing without an element buffer.
+    pub fn draw(
+        &mut self,
+        shader: &Shader<'gl>,
+        vao: &VertexArray<'gl>,
+        mode: PrimitiveKind,
+        first: u32,
+        count: u32,
+    ) {
+        if cfg!(debug_assertions) {
+            let capacity = if vao.index_kind().is_some() {
+                vao.index_capacity()
+            } else {
+                vao.vertex_capacity()
+            };
+            if let Some(capacity) = capacity {
+                let end = first + count;
+                assert!(
+                    end <= capacity,
+                    "index out of bounds: the size is {} but the end index is {}",
+                    capacity,
+                    end
+                );
+            }
+        }
+
+        let _shader = ShaderBindGuard::new(shader);
+        let vao = VertexArrayBindGuard::new(vao);
+        unsafe {
+            if vao.index_kind().is_some() {
+                vao.draw_elements(mode, first, count);
+            } else {
+                vao.draw_arrays(mode as u32, first, count);
+            }
+        }
+    }
+
+    /// Clear the depth buffer using [`clear_depth()`](Self::clear_depth).
+    #[inline]
+    pub fn clear_depth_buffer(&mut self) {
+        self.apply_clear_depth();
+        unsafe {
+            gl::Clear(gl::DEPTH_BUFFER_BIT);
+        }
+    }
+
+    /// Clear the stencil buffer using [`clear_stencil()`](Self::clear_stencil).
+    #[inline]
+    pub fn clear_stencil_buffer(&mut self) {
+        self.apply_clear_stencil();
+        unsafe {
+            gl::Clear(gl::STENCIL_BUFFER_BIT);
+        }
+    }
+
+    /// Read the color of the pixel at `(x, y)` of the bound framebuffer,
+    /// e.g. for picking objects rendered with unique colors as IDs.
+    ///
+    /// The position has its origin at the top-left corner, like cursor
+    /// positions, and is flipped using `height`, which must be the height
+    /// of the bound framebuffer, e.g. `wnd.get_framebuffer_size().1` for
+    /// the window. Components not included in `format` are `0`.
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # fn f(ctx: &mut RenderingContext<'_>, wnd: &Window) {
+    /// let (x, y) = wnd.get_cursor_pos();
+    /// let (_, height) = wnd.get_framebuffer_size();
+    /// let id = ctx.read_pixel(x as u32, y as u32, height as u32, PixelFormat::Rgba);
+    /// # }
+    /// ```
+    ///
+    /// This stalls until all previous rendering has completed.
+    pub fn read_pixel(&mut self, x: u32, y: u32, height: u32, format: PixelFormat) -> [u8; 4] {
+        debug_assert!(y < height);
+        let height = height as i32;
+
+        let mut pixel = [0u8; 4];
+        unsafe {
+            gl::ReadPixels(
+                x as i32,
+                height - 1 - (y as i32),
+                1,
+                1,
+                format as u32,
+                gl::UNSIGNED_BYTE,
+                pixel.as_mut_ptr() as *mut c_void,
+            );
+        }
+        pixel
+    }
+
+    /// Block until all previously issued commands have completed,
+    /// i.e. this stalls until the GPU is idle.
+    #[inline]
+    pub fn finish(&mut self) {
+        unsafe {
+            gl::Finish();
+        }
+    }
+
+    /// Ensure that memory accesses of the kinds in `barriers`, issued
+    /// after this call, observe writes from shaders issued before it,
+    /// e.g. [`MemoryBarrier::VERTEX_ATTRIB_ARRAY`] after a compute
+    /// shader wrote vertices to a shader storage buffer.
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # fn f(ctx: &mut RenderingContext<'_>) {
+    /// ctx.memory_barrier(MemoryBarrier::SHADER_STORAGE | MemoryBarrier::TEXTURE_FETCH);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn memory_barrier(&mut self, barriers: MemoryBarrier) {
+        unsafe {
+            gl::MemoryBarrier(barriers.bits());
+        }
+    }
+
+    /// Force all previously issued commands to be submitted
+    /// to the GPU, without waiting for them to complete.
+    #[inline]
+    pub fn flush(&mut self) {
+        unsafe {
+            gl::Flush();
+        }
+    }
+
     #[inline]
     pub fn create_buffer(&mut self) -> Buffer<'gl> {
         Buffer::new(self)
@@ -117,6 +969,49 @@ impl<'gl> RenderingContext<'gl> {
         Buffer::with_data(self, usage, data)
     }
 
+    #[inline]
+    pub fn create_ring_buffer(
+        &mut self,
+        segment_size: usize,
+        segment_count: usize,
+    ) -> RingBuffer<'gl> {
+        RingBuffer::new(self, segment_size, segment_count)
+    }
+
+    #[inline]
+    pub fn create_fence(&mut self) -> Fence<'gl> {
+        Fence::new(self)
+    }
+
+    #[inline]
+    pub fn create_framebuffer(&mut self) -> Framebuffer<'gl> {
+        Framebuffer::new(self)
+    }
+
+    #[inline]
+    pub fn create_query(&mut self, kind: QueryKind) -> Query<'gl> {
+        Query::new(self, kind)
+    }
+
+    #[inline]
+    pub fn create_renderbuffer(
+        &mut self,
+        size: (u32, u32),
+        internal_format: InternalFormat,
+    ) -> Renderbuffer<'gl> {
+        Renderbuffer::new(self, size, internal_format)
+    }
+
+    #[inline]
+    pub fn create_renderbuffer_multisample(
+        &mut self,
+        size: (u32, u32),
+        internal_format: InternalFormat,
+        samples: u32,
+    ) -> Renderbuffer<'gl> {
+        Renderbuffer::new_multisample(self, size, internal_format, samples)
+    }
+
     #[inline]
     pub fn create_vertex_array<'a>(
         &mut self,
@@ -128,6 +1023,11 @@ impl<'gl> RenderingContext<'gl> {
         VertexArray::new(self, desc)
     }
 
+    #[inline]
+    pub fn create_empty_vertex_array(&mut self) -> VertexArray<'gl> {
+        VertexArray::new_empty(self)
+    }
+
     #[inline]
     pub fn create_texture(
         &mut self,
@@ -137,6 +1037,26 @@ impl<'gl> RenderingContext<'gl> {
         Texture::new(self, size, internal_format)
     }
 
+    #[inline]
+    pub fn create_multisample_texture(
+        &mut self,
+        size: (u32, u32),
+        internal_format: InternalFormat,
+        samples: u32,
+    ) -> MultisampleTexture<'gl> {
+        MultisampleTexture::new(self, size, internal_format, samples)
+    }
+
+    #[inline]
+    pub fn create_texture_with_levels(
+        &mut self,
+        size: (u32, u32),
+        levels: u32,
+        internal_format: InternalFormat,
+    ) -> Texture<'gl> {
+        Texture::new_with_levels(self, size, levels, internal_format)
+    }
+
     #[inline]
     pub fn create_shader_stage(
         &mut self,
@@ -146,6 +1066,28 @@ impl<'gl> RenderingContext<'gl> {
         ShaderStage::new(self, kind, source)
     }
 
+    /// See [`ShaderStage::new_with_includes()`].
+    #[inline]
+    pub fn create_shader_stage_with_includes(
+        &mut self,
+        kind: ShaderStageKind,
+        source: impl AsRef<str>,
+        resolve: impl FnMut(&str) -> Option<String>,
+    ) -> Result<ShaderStage<'gl>, ShaderStageError> {
+        ShaderStage::new_with_includes(self, kind, source, resolve)
+    }
+
+    /// See [`ShaderStage::new_with_defines()`].
+    #[inline]
+    pub fn create_shader_stage_with_defines(
+        &mut self,
+        kind: ShaderStageKind,
+        source: impl AsRef<str>,
+        defines: &[(&str, &str)],
+    ) -> Result<ShaderStage<'gl>, ShaderStageError> {
+        ShaderStage::new_with_defines(self, kind, source, defines)
+    }
+
     #[inline]
     pub fn create_shader_stage_vertex(
         &mut self,
@@ -185,4 +1127,51 @@ impl<'gl> RenderingContext<'gl> {
     ) -> Result<Shader<'gl>, ShaderError> {
         Shader::new(self, stages)
     }
+
+    #[inline]
+    pub fn create_shader_from_combined(
+        &mut self,
+        source: impl AsRef<str>,
+    ) -> Result<Shader<'gl>, CombinedShaderError> {
+        Shader::new_from_combined(self, source)
+    }
+
+    #[inline]
+    pub fn create_shader_retrievable<'a>(
+        &mut self,
+        stages: &[impl AsRef<ShaderStage<'a>>],
+    ) -> Result<Shader<'gl>, ShaderError> {
+        Shader::new_retrievable(self, stages)
+    }
+
+    #[inline]
+    pub fn create_shader_with_attrib_locations<'a>(
+        &mut self,
+        stages: &[impl AsRef<ShaderStage<'a>>],
+        attrib_locations: &[(u32, &str)],
+    ) -> Result<Shader<'gl>, ShaderError> {
+        Shader::new_with_attrib_locations(self, stages, attrib_locations)
+    }
+
+    #[inline]
+    pub fn create_shader_from_binary(
+        &mut self,
+        format: u32,
+        binary: &[u8],
+    ) -> Result<Shader<'gl>, ShaderError> {
+        Shader::from_binary(self, format, binary)
+    }
+}
+
+pub(crate) fn print_warning(msg: &str) {
+    eprintln!("Warning: {}", msg);
+}
+
+#[inline]
+unsafe fn set_capability(cap: u32, enabled: bool) {
+    if enabled {
+        gl::Enable(cap);
+    } else {
+        gl::Disable(cap);
+    }
 }
@@ -0,0 +1,283 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use super::ShaderStageError;
+
+/// Maximum depth of nested `#include` directives,
+/// which also prevents infinite recursive includes.
+const MAX_INCLUDE_DEPTH: usize = 32;
+
+/// Replace all `#include "path"` and `#include <path>` directives
+/// with the source returned by `resolve`.
+///
+/// `#line` directives are inserted around each included source, such
+/// that line numbers in compile errors remain meaningful. The root
+/// source is source string number `0`, while each included source is
+/// given a subsequent source string number.
+pub(crate) fn resolve_includes(
+    source: &str,
+    resolve: &mut dyn FnMut(&str) -> Option<String>,
+) -> Result<String, ShaderStageError> {
+    let mut output = String::with_capacity(source.len());
+    let mut next_source_num = 1;
+    resolve_includes_into(source, 0, 0, resolve, &mut next_source_num, &mut output)?;
+    Ok(output)
+}
+
+fn resolve_includes_into(
+    source: &str,
+    source_num: u32,
+    depth: usize,
+    resolve: &mut dyn FnMut(&str) -> Option<String>,
+    next_source_num: &mut u32,
+    output: &mut String,
+) -> Result<(), ShaderStageError> {
+    for (i, line) in source.lines().enumerate() {
+        let Some(path) = parse_include(line) else {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        };
+
+        if depth >= MAX_INCLUDE_DEPTH {
+            return Err(ShaderStageError::IncludeDepth(path.to_owned()));
+        }
+
+        let included = resolve(path).ok_or_else(|| ShaderStageError::Include(path.to_owned()))?;
+
+        let included_num = *next_source_num;
+        *next_source_num += 1;
+
+        _ = writeln!(output, "#line 1 {included_num}");
+        resolve_includes_into(
+            &included,
+            included_num,
+            depth + 1,
+            resolve,
+            next_source_num,
+            output,
+        )?;
+        // `#line` specifies the number of the following line
+        _ = writeln!(output, "#line {} {source_num}", i + 2);
+    }
+    Ok(())
+}
+
+/// Prepend `#version {version}` unless `source` already
+/// has a `#version` directive.
+///
+/// A `#line` directive is inserted after the `#version`
+/// directive, such that line numbers remain unchanged.
+pub(crate) fn prepend_version<'a>(source: &'a str, version: Option<&str>) -> Cow<'a, str> {
+    match version {
+        Some(version) if find_version_directive(source).is_none() => {
+            Cow::Owned(format!("#version {version}\n#line 1 0\n{source}"))
+        }
+        _ => Cow::Borrowed(source),
+    }
+}
+
+/// Insert `#define name value` lines right after the `#version`
+/// directive, or at the start if `source` has no `#version` directive.
+///
+/// A `#line` directive is inserted after the defines, such that
+/// line numbers in compile errors remain meaningful.
+pub(crate) fn insert_defines(source: &str, defines: &[(&str, &str)]) -> String {
+    let (head, tail, version_lines) = match find_version_directive(source) {
+        Some((end, lines)) => {
+            let (head, tail) = source.split_at(end);
+            (head, tail, lines)
+        }
+        None => ("", source, 0),
+    };
+
+    let mut output = String::with_capacity(source.len());
+    output.push_str(head);
+    if !head.is_empty() && !head.ends_with('\n') {
+        output.push('\n');
+    }
+    for (name, value) in defines {
+        _ = writeln!(output, "#define {name} {value}");
+    }
+    _ = writeln!(output, "#line {} 0", version_lines + 1);
+    output.push_str(tail);
+    output
+}
+
+/// Returns the byte offset of the end of the `#version` directive line
+/// (including the line break) along with the number of lines up to and
+/// including it.
+///
+/// The `#version` directive must be the first directive, only preceded
+/// by empty lines and `//` comments, otherwise `None` is returned.
+pub(crate) fn find_version_directive(source: &str) -> Option<(usize, usize)> {
+    let mut end = 0;
+    for (i, line) in source.split_inclusive('\n').enumerate() {
+        end += line.len();
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+
+        let rest = trimmed.strip_prefix('#')?.trim_start();
+        return rest.starts_with("version").then_some((end, i + 1));
+    }
+    None
+}
+
+/// Returns `true` if `source` contains a conditional directive testing
+/// whether `name` is defined, i.e. `#ifdef name`, or an `#if` or `#elif`
+/// directive containing `defined(name)` or `defined name`.
+pub(crate) fn has_ifdef(source: &str, name: &str) -> bool {
+    source.lines().any(|line| {
+        let Some(rest) = line.trim_start().strip_prefix('#') else {
+            return false;
+        };
+        let mut idents = rest
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .filter(|ident| !ident.is_empty());
+        match idents.next() {
+            Some("ifdef") => idents.next() == Some(name),
+            Some("if" | "elif") => {
+                let idents = idents.collect::<Vec<_>>();
+                idents.windows(2).any(|w| w == ["defined", name])
+            }
+            _ => false,
+        }
+    })
+}
+
+fn parse_include(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix('#')?;
+    let rest = rest.trim_start().strip_prefix("include")?;
+    let rest = rest.trim();
+    let path = match rest.as_bytes().first()? {
+        b'"' => rest[1..].strip_suffix('"')?,
+        b'<' => rest[1..].strip_suffix('>')?,
+        _ => return None,
+    };
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(path: &str) -> Option<String> {
+        match path {
+            "x.glsl" => Some("x1\n#include <y.glsl>\nx3".to_owned()),
+            "y.glsl" => Some("y1".to_owned()),
+            "self.glsl" => Some("#include \"self.glsl\"".to_owned()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn nested_includes() {
+        let source = "a\n#include \"x.glsl\"\nb";
+        let output = resolve_includes(source, &mut resolve).unwrap();
+        assert_eq!(
+            output,
+            "a\n\
+             #line 1 1\n\
+             x1\n\
+             #line 1 2\n\
+             y1\n\
+             #line 3 1\n\
+             x3\n\
+             #line 3 0\n\
+             b\n"
+        );
+    }
+
+    #[test]
+    fn include_depth() {
+        let err = resolve_includes("#include \"self.glsl\"", &mut resolve).unwrap_err();
+        assert!(matches!(err, ShaderStageError::IncludeDepth(path) if path == "self.glsl"));
+    }
+
+    #[test]
+    fn missing_include() {
+        let err = resolve_includes("#include \"missing.glsl\"", &mut resolve).unwrap_err();
+        assert!(matches!(err, ShaderStageError::Include(path) if path == "missing.glsl"));
+    }
+
+    #[test]
+    fn defines_after_version() {
+        let output = insert_defines("#version 450\nvoid main() {}\n", &[("A", "1"), ("B", "2")]);
+        assert_eq!(
+            output,
+            "#version 450\n#define A 1\n#define B 2\n#line 2 0\nvoid main() {}\n"
+        );
+
+        let output = insert_defines("// comment\n\n#version 450\nx\n", &[("A", "1")]);
+        assert_eq!(
+            output,
+            "// comment\n\n#version 450\n#define A 1\n#line 4 0\nx\n"
+        );
+
+        let output = insert_defines("#version 450", &[("A", "1")]);
+        assert_eq!(output, "#version 450\n#define A 1\n#line 2 0\n");
+    }
+
+    #[test]
+    fn defines_without_version() {
+        let output = insert_defines("x\n", &[("A", "1")]);
+        assert_eq!(output, "#define A 1\n#line 1 0\nx\n");
+    }
+
+    #[test]
+    fn version_directive() {
+        assert_eq!(find_version_directive("#version 450\nx"), Some((13, 1)));
+        assert_eq!(
+            find_version_directive("// comment\n  # version 450 core\n"),
+            Some((32, 2))
+        );
+        assert_eq!(find_version_directive("#define A\n#version 450\n"), None);
+        assert_eq!(find_version_directive("void main() {}\n"), None);
+        assert_eq!(find_version_directive(""), None);
+    }
+
+    #[test]
+    fn ifdef_directive() {
+        assert!(has_ifdef("#ifdef GEOMETRY_SHADER\n", "GEOMETRY_SHADER"));
+        assert!(has_ifdef(
+            "x\n  # ifdef GEOMETRY_SHADER\n",
+            "GEOMETRY_SHADER"
+        ));
+        assert!(has_ifdef(
+            "#if defined(GEOMETRY_SHADER)\n",
+            "GEOMETRY_SHADER"
+        ));
+        assert!(has_ifdef(
+            "#elif defined GEOMETRY_SHADER\n",
+            "GEOMETRY_SHADER"
+        ));
+        assert!(has_ifdef(
+            "#if defined(VERTEX_SHADER) || defined(GEOMETRY_SHADER)\n",
+            "GEOMETRY_SHADER"
+        ));
+
+        assert!(!has_ifdef(
+            "// No GEOMETRY_SHADER stage\n",
+            "GEOMETRY_SHADER"
+        ));
+        assert!(!has_ifdef("#ifndef GEOMETRY_SHADER\n", "GEOMETRY_SHADER"));
+        assert!(!has_ifdef("#ifdef GEOMETRY_SHADER_X\n", "GEOMETRY_SHADER"));
+        assert!(!has_ifdef("#define GEOMETRY_SHADER\n", "GEOMETRY_SHADER"));
+    }
+
+    #[test]
+    fn prepend_missing_version() {
+        assert_eq!(
+            prepend_version("x\n", Some("450 core")),
+            "#version 450 core\n#line 1 0\nx\n"
+        );
+        assert_eq!(
+            prepend_version("#version 330\nx\n", Some("450")),
+            "#version 330\nx\n"
+        );
+        assert_eq!(prepend_version("x\n", None), "x\n");
+    }
+}
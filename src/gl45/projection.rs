@@ -0,0 +1,70 @@
+use glam::Mat4;
+
+use super::{ClipDepthMode, RenderingContext};
+
+impl RenderingContext<'_> {
+    /// Returns a right-handed perspective projection matrix, where `fov_y`
+    /// is the vertical field of view in radians, matching the current
+    /// clip space depth range, and reverse-Z if enabled, i.e. if the
+    /// depth test is [`Greater`](super::DepthFunc::Greater) or
+    /// [`GreaterEqual`](super::DepthFunc::GreaterEqual).
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # fn f(ctx: &mut RenderingContext<'_>, aspect: f32) {
+    /// ctx.enable_reverse_z();
+    /// let proj = ctx.perspective(60.0f32.to_radians(), aspect, 0.1, 1000.0);
+    /// # }
+    /// ```
+    pub fn perspective(&self, fov_y: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
+        let (near, far) = if self.is_reverse_z() {
+            (far, near)
+        } else {
+            (near, far)
+        };
+        match self.clip_depth_mode() {
+            ClipDepthMode::NegativeOneToOne => Mat4::perspective_rh_gl(fov_y, aspect, near, far),
+            ClipDepthMode::ZeroToOne => Mat4::perspective_rh(fov_y, aspect, near, far),
+        }
+    }
+
+    /// Returns a right-handed orthographic projection matrix, matching
+    /// the current clip space depth range, and reverse-Z if enabled,
+    /// see [`RenderingContext::perspective()`].
+    pub fn ortho(&self, left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4 {
+        let (near, far) = if self.is_reverse_z() {
+            (far, near)
+        } else {
+            (near, far)
+        };
+        match self.clip_depth_mode() {
+            ClipDepthMode::NegativeOneToOne => {
+                Mat4::orthographic_rh_gl(left, right, bottom, top, near, far)
+            }
+            ClipDepthMode::ZeroToOne => Mat4::orthographic_rh(left, right, bottom, top, near, far),
+        }
+    }
+
+    // The conventions are queried instead of read from the state
+    // cache, as the cache is forgotten by `invalidate_state_cache()`
+
+    fn clip_depth_mode(&self) -> ClipDepthMode {
+        let mut depth_mode = 0;
+        unsafe {
+            gl::GetIntegerv(gl::CLIP_DEPTH_MODE, &mut depth_mode);
+        }
+        if depth_mode as u32 == gl::ZERO_TO_ONE {
+            ClipDepthMode::ZeroToOne
+        } else {
+            ClipDepthMode::NegativeOneToOne
+        }
+    }
+
+    fn is_reverse_z(&self) -> bool {
+        let mut func = 0;
+        unsafe {
+            gl::GetIntegerv(gl::DEPTH_FUNC, &mut func);
+        }
+        matches!(func as u32, gl::GREATER | gl::GEQUAL)
+    }
+}
@@ -0,0 +1,142 @@
+pub mod prelude {
+    pub use super::{ConditionalRenderMode, Query, QueryKind};
+}
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::{GLHandle, RenderingContext};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum QueryKind {
+    /// Counts the number of samples passing the depth test.
+    SamplesPassed = gl::SAMPLES_PASSED,
+    /// Whether any samples passed the depth test.
+    AnySamplesPassed = gl::ANY_SAMPLES_PASSED,
+    /// Like [`AnySamplesPassed`](Self::AnySamplesPassed), but allows
+    /// the implementation to be less precise for better performance.
+    AnySamplesPassedConservative = gl::ANY_SAMPLES_PASSED_CONSERVATIVE,
+    PrimitivesGenerated = gl::PRIMITIVES_GENERATED,
+    /// Measures the elapsed GPU time in nanoseconds.
+    TimeElapsed = gl::TIME_ELAPSED,
+}
+
+/// How [`RenderingContext::begin_conditional_render()`] waits
+/// for the result of the query.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum ConditionalRenderMode {
+    /// Wait for the query result before rendering.
+    Wait = gl::QUERY_WAIT,
+    /// Render unconditionally, if the query result is not yet available.
+    NoWait = gl::QUERY_NO_WAIT,
+    ByRegionWait = gl::QUERY_BY_REGION_WAIT,
+    ByRegionNoWait = gl::QUERY_BY_REGION_NO_WAIT,
+}
+
+pub struct Query<'gl> {
+    handle: u32,
+    kind: QueryKind,
+    phantom: PhantomData<&'gl ()>,
+}
+
+impl Query<'static> {
+    /// # Safety
+    ///
+    /// Must only be called on a thread where there is a current
+    /// OpenGL context. The returned `Query` must only
+    /// exist, while the OpenGL context is valid.
+    #[inline]
+    pub unsafe fn new_unsafe(kind: QueryKind) -> Self {
+        Self::create(kind)
+    }
+}
+
+impl<'gl> Query<'gl> {
+    #[inline]
+    pub fn new(_ctx: &mut RenderingContext<'gl>, kind: QueryKind) -> Self {
+        Self::create(kind)
+    }
+
+    fn create(kind: QueryKind) -> Self {
+        let mut handle = 0;
+        unsafe {
+            gl::CreateQueries(kind as u32, 1, &mut handle);
+        }
+        debug_assert_ne!(handle, 0, "failed creating query");
+        Self {
+            handle,
+            kind,
+            phantom: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn kind(&self) -> QueryKind {
+        self.kind
+    }
+
+    /// Begin counting for all subsequent commands, until [`end()`](Self::end)
+    /// is called. Only a single query of each kind can be active at a time.
+    ///
+    /// # Safety
+    ///
+    /// No other query of the same kind must be active, and [`end()`](Self::end)
+    /// must be called before the query is dropped.
+    #[inline]
+    pub unsafe fn begin(&self) {
+        gl::BeginQuery(self.kind as u32, self.handle);
+    }
+
+    /// Stop counting, after which the result becomes available
+    /// once all previous commands have completed.
+    ///
+    /// # Safety
+    ///
+    /// The query must be active, i.e. [`begin()`](Self::begin) must
+    /// have been called.
+    #[inline]
+    pub unsafe fn end(&self) {
+        gl::EndQuery(self.kind as u32);
+    }
+
+    /// Returns `true` if the result is available, without blocking.
+    pub fn is_result_available(&self) -> bool {
+        let mut available = 0;
+        unsafe {
+            gl::GetQueryObjectiv(self.handle, gl::QUERY_RESULT_AVAILABLE, &mut available);
+        }
+        available != 0
+    }
+
+    /// Returns the result of the query, blocking until it is available.
+    pub fn result(&self) -> u64 {
+        let mut result = 0;
+        unsafe {
+            gl::GetQueryObjectui64v(self.handle, gl::QUERY_RESULT, &mut result);
+        }
+        result
+    }
+}
+
+impl GLHandle for Query<'_> {
+    #[inline]
+    unsafe fn gl_handle(&self) -> u32 {
+        self.handle
+    }
+}
+
+impl Drop for Query<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteQueries(1, &self.handle);
+        }
+    }
+}
+
+impl fmt::Debug for Query<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Query({}, {:?})", self.handle, self.kind)
+    }
+}
@@ -0,0 +1,124 @@
+pub mod prelude {
+    pub use super::Renderbuffer;
+}
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::{GLHandle, InternalFormat, RenderingContext};
+
+/// Render target which cannot be sampled, e.g. a depth buffer,
+/// which is cheaper than a depth texture, when it is only used
+/// for depth testing.
+pub struct Renderbuffer<'gl> {
+    handle: u32,
+    size: (u32, u32),
+    samples: u32,
+    phantom: PhantomData<&'gl ()>,
+}
+
+impl Renderbuffer<'static> {
+    /// # Safety
+    ///
+    /// Must only be called on a thread where there is a current
+    /// OpenGL context. The returned `Renderbuffer` must only
+    /// exist, while the OpenGL context is valid.
+    #[inline]
+    pub unsafe fn new_unsafe(size: (u32, u32), internal_format: InternalFormat) -> Self {
+        Self::create(size, internal_format, 0)
+    }
+}
+
+impl<'gl> Renderbuffer<'gl> {
+    #[inline]
+    pub fn new(
+        _ctx: &mut RenderingContext<'gl>,
+        size: (u32, u32),
+        internal_format: InternalFormat,
+    ) -> Self {
+        Self::create(size, internal_format, 0)
+    }
+
+    /// Create a multisampled renderbuffer with `samples` samples per pixel,
+    /// e.g. for an MSAA render target.
+    #[inline]
+    pub fn new_multisample(
+        _ctx: &mut RenderingContext<'gl>,
+        size: (u32, u32),
+        internal_format: InternalFormat,
+        samples: u32,
+    ) -> Self {
+        Self::create(size, internal_format, samples)
+    }
+
+    fn create(size: (u32, u32), internal_format: InternalFormat, samples: u32) -> Self {
+        let rb = {
+            let mut handle = 0;
+            unsafe {
+                gl::CreateRenderbuffers(1, &mut handle);
+            }
+            debug_assert_ne!(handle, 0, "failed creating renderbuffer");
+            // Constructed early to ensure `gl::DeleteRenderbuffers()` is called on error
+            Self {
+                handle,
+                size,
+                samples,
+                phantom: PhantomData,
+            }
+        };
+
+        unsafe {
+            if samples > 0 {
+                gl::NamedRenderbufferStorageMultisample(
+                    rb.handle,
+                    samples as i32,
+                    internal_format as u32,
+                    size.0 as i32,
+                    size.1 as i32,
+                );
+            } else {
+                gl::NamedRenderbufferStorage(
+                    rb.handle,
+                    internal_format as u32,
+                    size.0 as i32,
+                    size.1 as i32,
+                );
+            }
+        }
+
+        rb
+    }
+
+    #[inline]
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Returns the number of samples per pixel,
+    /// which is `0` if not multisampled.
+    #[inline]
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+}
+
+impl GLHandle for Renderbuffer<'_> {
+    #[inline]
+    unsafe fn gl_handle(&self) -> u32 {
+        self.handle
+    }
+}
+
+impl Drop for Renderbuffer<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteRenderbuffers(1, &self.handle);
+        }
+    }
+}
+
+impl fmt::Debug for Renderbuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Renderbuffer({}, {:?})", self.handle, self.size)
+    }
+}
@@ -0,0 +1,154 @@
+pub mod prelude {
+    pub use super::RingBuffer;
+}
+
+use std::fmt;
+use std::slice;
+use std::time::Duration;
+
+use super::{Buffer, Fence, GLHandle, RenderingContext};
+
+/// Persistently mapped buffer split into `segment_count` segments,
+/// which are written to in a round-robin fashion, e.g. for streaming
+/// uniform or vertex data every frame.
+///
+/// Each segment is fenced after use, such that writing to a segment
+/// waits until the GPU is done reading the previous data in it.
+///
+/// ```no_run
+/// # use ren::prelude::*;
+/// # fn f(ring: &mut RingBuffer<'_>, bytes: &[u8]) {
+/// let data = ring.segment_mut();
+/// data[..bytes.len()].copy_from_slice(bytes);
+/// // Draw using `ring.buffer()` at `ring.segment_offset()`
+/// ring.finish_segment();
+/// # }
+/// ```
+pub struct RingBuffer<'gl> {
+    buffer: Buffer<'gl>,
+    ptr: *mut u8,
+    segment_size: usize,
+    fences: Vec<Option<Fence<'gl>>>,
+    index: usize,
+}
+
+impl<'gl> RingBuffer<'gl> {
+    /// Create `segment_count` segments of at least `segment_size` bytes.
+    ///
+    /// The segment size is rounded up to `GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT`
+    /// and `GL_SHADER_STORAGE_BUFFER_OFFSET_ALIGNMENT`, such that each
+    /// [`segment_offset()`](Self::segment_offset) can be bound as a uniform
+    /// or shader storage buffer range, see [`segment_size()`](Self::segment_size).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `segment_size` or `segment_count` is zero,
+    /// or if mapping the buffer failed.
+    pub fn new(ctx: &mut RenderingContext<'gl>, segment_size: usize, segment_count: usize) -> Self {
+        assert!(segment_size > 0, "segment size must be greater than zero");
+        assert!(segment_count > 0, "segment count must be greater than zero");
+
+        let align = [
+            gl::UNIFORM_BUFFER_OFFSET_ALIGNMENT,
+            gl::SHADER_STORAGE_BUFFER_OFFSET_ALIGNMENT,
+        ]
+        .into_iter()
+        .map(|pname| {
+            let mut align = 0;
+            unsafe {
+                gl::GetIntegerv(pname, &mut align);
+            }
+            align.max(1) as usize
+        })
+        .max()
+        .unwrap();
+        let segment_size = segment_size.div_ceil(align) * align;
+
+        let size = segment_size * segment_count;
+        let flags = gl::MAP_WRITE_BIT | gl::MAP_PERSISTENT_BIT | gl::MAP_COHERENT_BIT;
+
+        let mut buffer = Buffer::new(ctx);
+        let ptr = unsafe {
+            buffer.init_storage(size, flags);
+            buffer.map_range(0, size, flags)
+        };
+        assert!(!ptr.is_null(), "failed mapping ring buffer");
+
+        Self {
+            buffer,
+            ptr: ptr as *mut u8,
+            segment_size,
+            fences: (0..segment_count).map(|_| None).collect(),
+            index: 0,
+        }
+    }
+
+    /// Returns the current segment, waiting until
+    /// the GPU is done using its previous data.
+    pub fn segment_mut(&mut self) -> &mut [u8] {
+        if let Some(fence) = self.fences[self.index].take() {
+            fence.wait(Duration::MAX);
+        }
+
+        unsafe { slice::from_raw_parts_mut(self.ptr.add(self.segment_offset()), self.segment_size) }
+    }
+
+    /// Fence the current segment and advance to the next segment.
+    ///
+    /// Must be called after issuing all commands using the current segment.
+    pub fn finish_segment(&mut self) {
+        self.fences[self.index] = Some(Fence::create());
+
+        self.index = (self.index + 1) % self.fences.len();
+    }
+
+    /// Returns the byte offset of the current segment into [`buffer()`](Self::buffer).
+    #[inline]
+    pub fn segment_offset(&self) -> usize {
+        self.index * self.segment_size
+    }
+
+    /// Returns the byte size of each segment, which may be larger
+    /// than requested, see [`RingBuffer::new()`].
+    #[inline]
+    pub fn segment_size(&self) -> usize {
+        self.segment_size
+    }
+
+    #[inline]
+    pub fn segment_count(&self) -> usize {
+        self.fences.len()
+    }
+
+    #[inline]
+    pub fn buffer(&self) -> &Buffer<'gl> {
+        &self.buffer
+    }
+}
+
+impl GLHandle for RingBuffer<'_> {
+    #[inline]
+    unsafe fn gl_handle(&self) -> u32 {
+        self.buffer.gl_handle()
+    }
+}
+
+impl Drop for RingBuffer<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            self.buffer.unmap();
+        }
+    }
+}
+
+impl fmt::Debug for RingBuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RingBuffer({}, {}x{})",
+            unsafe { self.buffer.gl_handle() },
+            self.segment_count(),
+            self.segment_size,
+        )
+    }
+}
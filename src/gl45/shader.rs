@@ -1,16 +1,20 @@
 pub mod prelude {
-    pub use super::{Shader, ShaderError, ShaderStage, ShaderStageError, ShaderStageKind};
+    pub use super::{
+        CombinedShaderError, Shader, ShaderError, ShaderStage, ShaderStageError, ShaderStageKind,
+    };
 }
 
 use std::borrow::Cow;
-use std::ffi::{c_char, CStr};
+use std::ffi::{c_char, CStr, CString};
 use std::fmt;
 use std::marker::PhantomData;
+use std::mem;
 use std::ptr;
 
 use thiserror::Error;
 
-use super::{GLHandle, RawGLHandle, RenderingContext, UniformLocation};
+use super::preprocess::{has_ifdef, insert_defines, prepend_version, resolve_includes};
+use super::{print_warning, GLHandle, RawGLHandle, RenderingContext, UniformLocation};
 
 macro_rules! c_str {
     ($s:literal) => {
@@ -55,53 +59,119 @@ impl ShaderStage<'static> {
         kind: ShaderStageKind,
         source: impl AsRef<str>,
     ) -> Result<Self, ShaderStageError> {
-        Self::create(kind, source)
+        Self::create(kind, source, &mut print_warning)
     }
 }
 
 impl<'gl> ShaderStage<'gl> {
     #[inline]
     pub fn new(
-        _ctx: &mut RenderingContext<'gl>,
+        ctx: &mut RenderingContext<'gl>,
+        kind: ShaderStageKind,
+        source: impl AsRef<str>,
+    ) -> Result<Self, ShaderStageError> {
+        Self::create_with_ctx(ctx, kind, source.as_ref())
+    }
+
+    /// Same as [`new()`](Self::new), but first resolves all
+    /// `#include "path"` directives, by replacing them with
+    /// the source returned by `resolve(path)`.
+    ///
+    /// Included sources can themselves contain `#include` directives.
+    /// `#line` directives are inserted, such that line numbers in compile
+    /// errors remain meaningful. Each included source is given its own
+    /// source string number, starting at `1` in order of inclusion.
+    ///
+    /// ```no_run
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use ren::prelude::*;
+    /// # fn f(ctx: &mut RenderingContext<'_>, kind: ShaderStageKind, source: &str) -> Result<(), ShaderStageError> {
+    /// let stage = ShaderStage::new_with_includes(ctx, kind, source, |path| {
+    ///     fs::read_to_string(Path::new("shaders").join(path)).ok()
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_includes(
+        ctx: &mut RenderingContext<'gl>,
         kind: ShaderStageKind,
         source: impl AsRef<str>,
+        mut resolve: impl FnMut(&str) -> Option<String>,
     ) -> Result<Self, ShaderStageError> {
-        Self::create(kind, source)
+        let source = resolve_includes(source.as_ref(), &mut resolve)?;
+        Self::create_with_ctx(ctx, kind, &source)
+    }
+
+    /// Same as [`new()`](Self::new), but first inserts a
+    /// `#define name value` line for each of `defines`,
+    /// right after the `#version` directive.
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # fn f(ctx: &mut RenderingContext<'_>, kind: ShaderStageKind, source: &str) -> Result<(), ShaderStageError> {
+    /// let stage = ShaderStage::new_with_defines(ctx, kind, source, &[("USE_FOG", "1")])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_defines(
+        ctx: &mut RenderingContext<'gl>,
+        kind: ShaderStageKind,
+        source: impl AsRef<str>,
+        defines: &[(&str, &str)],
+    ) -> Result<Self, ShaderStageError> {
+        let source = insert_defines(source.as_ref(), defines);
+        Self::create_with_ctx(ctx, kind, &source)
     }
 
     #[inline]
     pub fn new_vertex(
-        _ctx: &mut RenderingContext<'gl>,
+        ctx: &mut RenderingContext<'gl>,
         source: impl AsRef<str>,
     ) -> Result<Self, ShaderStageError> {
-        Self::create(ShaderStageKind::Vertex, source)
+        Self::create_with_ctx(ctx, ShaderStageKind::Vertex, source.as_ref())
     }
 
     #[inline]
     pub fn new_fragment(
-        _ctx: &mut RenderingContext<'gl>,
+        ctx: &mut RenderingContext<'gl>,
         source: impl AsRef<str>,
     ) -> Result<Self, ShaderStageError> {
-        Self::create(ShaderStageKind::Fragment, source)
+        Self::create_with_ctx(ctx, ShaderStageKind::Fragment, source.as_ref())
     }
 
     #[inline]
     pub fn new_geometry(
-        _ctx: &mut RenderingContext<'gl>,
+        ctx: &mut RenderingContext<'gl>,
         source: impl AsRef<str>,
     ) -> Result<Self, ShaderStageError> {
-        Self::create(ShaderStageKind::Geometry, source)
+        Self::create_with_ctx(ctx, ShaderStageKind::Geometry, source.as_ref())
     }
 
     #[inline]
     pub fn new_compute(
-        _ctx: &mut RenderingContext<'gl>,
+        ctx: &mut RenderingContext<'gl>,
         source: impl AsRef<str>,
     ) -> Result<Self, ShaderStageError> {
-        Self::create(ShaderStageKind::Compute, source)
+        Self::create_with_ctx(ctx, ShaderStageKind::Compute, source.as_ref())
     }
 
-    fn create(kind: ShaderStageKind, source: impl AsRef<str>) -> Result<Self, ShaderStageError> {
+    /// Prepends the [GLSL version](RenderingContext::set_glsl_version)
+    /// of `ctx`, if any, before compiling.
+    fn create_with_ctx(
+        ctx: &mut RenderingContext<'gl>,
+        kind: ShaderStageKind,
+        source: &str,
+    ) -> Result<Self, ShaderStageError> {
+        let source = prepend_version(source, ctx.glsl_version());
+        Self::create(kind, source, ctx.warning_callback())
+    }
+
+    fn create(
+        kind: ShaderStageKind,
+        source: impl AsRef<str>,
+        warn: &mut dyn FnMut(&str),
+    ) -> Result<Self, ShaderStageError> {
         let mut shader = {
             let handle = unsafe { gl::CreateShader(kind as u32) };
             debug_assert_ne!(handle, 0, "failed creating {} shader stage", kind.name());
@@ -112,11 +182,15 @@ impl<'gl> ShaderStage<'gl> {
                 phantom: PhantomData,
             }
         };
-        shader.compile(source)?;
+        shader.compile(source, warn)?;
         Ok(shader)
     }
 
-    fn compile(&mut self, source: impl AsRef<str>) -> Result<(), ShaderStageError> {
+    fn compile(
+        &mut self,
+        source: impl AsRef<str>,
+        warn: &mut dyn FnMut(&str),
+    ) -> Result<(), ShaderStageError> {
         let source = source.as_ref();
         unsafe {
             gl::ShaderSource(
@@ -140,11 +214,11 @@ impl<'gl> ShaderStage<'gl> {
 
         if is_compiled {
             if let Some(log) = &log {
-                eprintln!(
-                    "Warning: Compiling {} shader stage:\n{}",
+                warn(&format!(
+                    "Compiling {} shader stage:\n{}",
                     self.kind.name(),
                     log.trim(),
-                );
+                ));
             }
 
             Ok(())
@@ -204,20 +278,118 @@ impl Shader<'static> {
     pub unsafe fn new_unsafe<'a>(
         stages: &[impl AsRef<ShaderStage<'a>>],
     ) -> Result<Self, ShaderError> {
-        Self::create(stages)
+        Self::create(stages, false, &[], &mut print_warning)
+    }
+
+    /// # Safety
+    ///
+    /// Must only be called on a thread where there is a current
+    /// OpenGL context. The returned `Shader` must only
+    /// exist, while the OpenGL context is valid.
+    #[inline]
+    pub unsafe fn from_binary_unsafe(format: u32, binary: &[u8]) -> Result<Self, ShaderError> {
+        Self::create_from_binary(format, binary, &mut print_warning)
     }
 }
 
 impl<'gl> Shader<'gl> {
     #[inline]
     pub fn new<'a>(
-        _ctx: &mut RenderingContext<'gl>,
+        ctx: &mut RenderingContext<'gl>,
         stages: &[impl AsRef<ShaderStage<'a>>],
     ) -> Result<Self, ShaderError> {
-        Self::create(stages)
+        Self::create(stages, false, &[], ctx.warning_callback())
     }
 
-    fn create<'a>(stages: &[impl AsRef<ShaderStage<'a>>]) -> Result<Self, ShaderError> {
+    /// Create a shader program from a single `source` containing all stages,
+    /// separated by `#ifdef VERTEX_SHADER` and `#ifdef FRAGMENT_SHADER`,
+    /// i.e. the source is compiled once per stage with the respective
+    /// define. If `source` contains an `#ifdef GEOMETRY_SHADER` or
+    /// `#if defined(GEOMETRY_SHADER)` directive, then a geometry
+    /// stage is compiled as well.
+    ///
+    /// ```glsl
+    /// #ifdef VERTEX_SHADER
+    /// layout(location = 0) in vec3 in_pos;
+    /// void main() { gl_Position = vec4(in_pos, 1.0); }
+    /// #endif
+    ///
+    /// #ifdef FRAGMENT_SHADER
+    /// out vec4 out_color;
+    /// void main() { out_color = vec4(1.0); }
+    /// #endif
+    /// ```
+    pub fn new_from_combined(
+        ctx: &mut RenderingContext<'gl>,
+        source: impl AsRef<str>,
+    ) -> Result<Self, CombinedShaderError> {
+        let source = source.as_ref();
+
+        let mut stages = vec![
+            ShaderStage::new_with_defines(
+                ctx,
+                ShaderStageKind::Vertex,
+                source,
+                &[("VERTEX_SHADER", "1")],
+            )?,
+            ShaderStage::new_with_defines(
+                ctx,
+                ShaderStageKind::Fragment,
+                source,
+                &[("FRAGMENT_SHADER", "1")],
+            )?,
+        ];
+        if has_ifdef(source, "GEOMETRY_SHADER") {
+            stages.push(ShaderStage::new_with_defines(
+                ctx,
+                ShaderStageKind::Geometry,
+                source,
+                &[("GEOMETRY_SHADER", "1")],
+            )?);
+        }
+
+        Ok(Self::new(ctx, &stages)?)
+    }
+
+    /// Same as [`Shader::new()`], but hints the driver that the binary
+    /// will be retrieved using [`Shader::get_binary()`], i.e. sets
+    /// `GL_PROGRAM_BINARY_RETRIEVABLE_HINT` before linking.
+    #[inline]
+    pub fn new_retrievable<'a>(
+        ctx: &mut RenderingContext<'gl>,
+        stages: &[impl AsRef<ShaderStage<'a>>],
+    ) -> Result<Self, ShaderError> {
+        Self::create(stages, true, &[], ctx.warning_callback())
+    }
+
+    /// Same as [`Shader::new()`], but binds each `(index, name)` vertex
+    /// attribute to location `index` before linking, e.g. for shaders
+    /// without `layout(location = index)` qualifiers.
+    ///
+    /// Returns [`ShaderError::AttribName`] if any `name` contains a nul byte.
+    #[inline]
+    pub fn new_with_attrib_locations<'a>(
+        ctx: &mut RenderingContext<'gl>,
+        stages: &[impl AsRef<ShaderStage<'a>>],
+        attrib_locations: &[(u32, &str)],
+    ) -> Result<Self, ShaderError> {
+        Self::create(stages, false, attrib_locations, ctx.warning_callback())
+    }
+
+    fn create<'a>(
+        stages: &[impl AsRef<ShaderStage<'a>>],
+        retrievable: bool,
+        attrib_locations: &[(u32, &str)],
+        warn: &mut dyn FnMut(&str),
+    ) -> Result<Self, ShaderError> {
+        let attrib_locations = attrib_locations
+            .iter()
+            .map(|&(index, name)| match CString::new(name) {
+                Ok(name) => Ok((index, name)),
+                Err(_) => Err(ShaderError::AttribName(name.to_owned())),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
         let mut shader = {
             let handle = unsafe { gl::CreateProgram() };
             debug_assert_ne!(handle, 0, "failed creating shader program");
@@ -227,13 +399,30 @@ impl<'gl> Shader<'gl> {
                 phantom: PhantomData,
             }
         };
+        if retrievable {
+            unsafe {
+                gl::ProgramParameteri(
+                    shader.handle,
+                    gl::PROGRAM_BINARY_RETRIEVABLE_HINT,
+                    gl::TRUE as i32,
+                );
+            }
+        }
         unsafe {
             attach_shaders(
                 shader.handle,
                 stages.iter().map(|stage| stage.as_ref().handle),
             );
         }
-        let res = shader.init();
+        for (index, name) in &attrib_locations {
+            unsafe {
+                gl::BindAttribLocation(shader.handle, *index, name.as_ptr());
+            }
+        }
+        let has_geometry_stage = stages
+            .iter()
+            .any(|stage| stage.as_ref().kind == ShaderStageKind::Geometry);
+        let res = shader.init(has_geometry_stage, warn);
         unsafe {
             detach_shaders(
                 shader.handle,
@@ -246,6 +435,59 @@ impl<'gl> Shader<'gl> {
         }
     }
 
+    /// Create a shader program from a `binary` previously returned by
+    /// [`Shader::get_binary()`], e.g. cached on disk to avoid compiling
+    /// shaders on every startup.
+    ///
+    /// Returns [`ShaderError::Binary`] if the driver rejects the binary,
+    /// e.g. after a driver update, in which case the caller should fall
+    /// back to compiling the shader from source.
+    #[inline]
+    pub fn from_binary(
+        ctx: &mut RenderingContext<'gl>,
+        format: u32,
+        binary: &[u8],
+    ) -> Result<Self, ShaderError> {
+        Self::create_from_binary(format, binary, ctx.warning_callback())
+    }
+
+    fn create_from_binary(
+        format: u32,
+        binary: &[u8],
+        warn: &mut dyn FnMut(&str),
+    ) -> Result<Self, ShaderError> {
+        let mut shader = {
+            let handle = unsafe { gl::CreateProgram() };
+            debug_assert_ne!(handle, 0, "failed creating shader program");
+            // Constructed early to ensure `gl::DeleteProgram()` is called on error
+            Self {
+                handle,
+                phantom: PhantomData,
+            }
+        };
+
+        unsafe {
+            gl::ProgramBinary(
+                shader.handle,
+                format,
+                binary.as_ptr() as *const _,
+                binary.len() as i32,
+            );
+        }
+
+        let is_linked = unsafe {
+            let mut status = 0;
+            gl::GetProgramiv(shader.handle, gl::LINK_STATUS, &mut status);
+            status == 1
+        };
+        shader
+            .check_log("Loading", is_linked, warn)
+            .map_err(|log| ShaderError::Binary(RawGLHandle(shader.handle), log))?;
+        shader.validate(warn)?;
+
+        Ok(shader)
+    }
+
     #[inline]
     fn bind_data_locations(&mut self) {
         unsafe {
@@ -253,7 +495,11 @@ impl<'gl> Shader<'gl> {
         }
     }
 
-    fn link(&mut self) -> Result<(), ShaderError> {
+    fn link(
+        &mut self,
+        has_geometry_stage: bool,
+        warn: &mut dyn FnMut(&str),
+    ) -> Result<(), ShaderError> {
         unsafe {
             gl::LinkProgram(self.handle);
         }
@@ -263,11 +509,32 @@ impl<'gl> Shader<'gl> {
             gl::GetProgramiv(self.handle, gl::LINK_STATUS, &mut status);
             status == 1
         };
-        self.check_log("Linking", is_linked)
-            .map_err(|log| ShaderError::Link(RawGLHandle(self.handle), log))
-    }
-
-    fn validate(&mut self) -> Result<(), ShaderError> {
+        self.check_log("Linking", is_linked, warn).map_err(|log| {
+            // Exceeding the geometry shader output limits results in
+            // fairly cryptic logs, so include the limits in the error
+            let log = if has_geometry_stage {
+                let (max_vertices, max_components) = unsafe {
+                    let (mut max_vertices, mut max_components) = (0, 0);
+                    gl::GetIntegerv(gl::MAX_GEOMETRY_OUTPUT_VERTICES, &mut max_vertices);
+                    gl::GetIntegerv(
+                        gl::MAX_GEOMETRY_TOTAL_OUTPUT_COMPONENTS,
+                        &mut max_components,
+                    );
+                    (max_vertices, max_components)
+                };
+                Cow::Owned(format!(
+                    "{}\n(geometry shader limits: max output vertices = {max_vertices}, \
+                     max total output components = {max_components})",
+                    log.trim_end(),
+                ))
+            } else {
+                log
+            };
+            ShaderError::Link(RawGLHandle(self.handle), log)
+        })
+    }
+
+    fn validate(&mut self, warn: &mut dyn FnMut(&str)) -> Result<(), ShaderError> {
         unsafe {
             gl::ValidateProgram(self.handle);
         }
@@ -277,15 +544,20 @@ impl<'gl> Shader<'gl> {
             gl::GetProgramiv(self.handle, gl::VALIDATE_STATUS, &mut status);
             status == 1
         };
-        self.check_log("Validating", is_validated)
+        self.check_log("Validating", is_validated, warn)
             .map_err(|log| ShaderError::Validation(RawGLHandle(self.handle), log))
     }
 
-    fn check_log(&self, op: &str, was_success: bool) -> Result<(), Cow<'static, str>> {
+    fn check_log(
+        &self,
+        op: &str,
+        was_success: bool,
+        warn: &mut dyn FnMut(&str),
+    ) -> Result<(), Cow<'static, str>> {
         let log = get_program_info_log(self.handle);
         if was_success {
             if let Some(log) = &log {
-                eprintln!("Warning: {op} shader program:\n{}", log.trim());
+                warn(&format!("{op} shader program:\n{}", log.trim()));
             }
             Ok(())
         } else {
@@ -296,10 +568,14 @@ impl<'gl> Shader<'gl> {
         }
     }
 
-    fn init(&mut self) -> Result<(), ShaderError> {
+    fn init(
+        &mut self,
+        has_geometry_stage: bool,
+        warn: &mut dyn FnMut(&str),
+    ) -> Result<(), ShaderError> {
         self.bind_data_locations();
-        self.link()?;
-        self.validate()?;
+        self.link(has_geometry_stage, warn)?;
+        self.validate(warn)?;
         Ok(())
     }
 
@@ -308,14 +584,21 @@ impl<'gl> Shader<'gl> {
         gl::UseProgram(self.handle);
     }
 
-    /// Returns `None` if `name` does not correspond to an active uniform variable.
-    ///
-    /// Panics if `name` contains a nul byte.
+    /// Returns `None` if `name` does not correspond to an active uniform variable,
+    /// including if `name` contains a nul byte.
     #[inline]
     pub fn get_uniform_location(&self, name: impl AsRef<str>) -> Option<UniformLocation> {
         UniformLocation::get_uniform_location(self.handle, name)
     }
 
+    /// Returns `true` if `name` corresponds to an active uniform variable.
+    ///
+    /// Never panics, i.e. returns `false` if `name` contains a nul byte.
+    #[inline]
+    pub fn has_uniform(&self, name: impl AsRef<str>) -> bool {
+        self.get_uniform_location(name).is_some()
+    }
+
     /// Returns `None` if `name` does not correspond to an active uniform variable.
     ///
     /// Panics if `name` does not end with a nul byte, or
@@ -348,6 +631,47 @@ impl<'gl> Shader<'gl> {
     ) -> Option<UniformLocation> {
         UniformLocation::get_uniform_location_from_c_char_ptr(self.handle, name)
     }
+
+    /// Returns the `(format, binary)` of the linked shader program,
+    /// which can be loaded using [`Shader::from_binary()`].
+    ///
+    /// The binary is only valid for the same driver and hardware,
+    /// so it should be cached locally and not distributed.
+    ///
+    /// Some drivers return an empty binary, unless the shader program
+    /// was created using [`Shader::new_retrievable()`].
+    pub fn get_binary(&self) -> (u32, Vec<u8>) {
+        let mut len = 0;
+        unsafe {
+            gl::GetProgramiv(self.handle, gl::PROGRAM_BINARY_LENGTH, &mut len);
+        }
+
+        let mut binary = vec![0u8; len as usize];
+        let mut format = 0;
+        let mut written = 0;
+        unsafe {
+            gl::GetProgramBinary(
+                self.handle,
+                len,
+                &mut written,
+                &mut format,
+                binary.as_mut_ptr() as *mut _,
+            );
+        }
+        binary.truncate(written as usize);
+
+        (format, binary)
+    }
+
+    /// Returns the handle without deleting the shader program, i.e. the
+    /// caller becomes responsible for calling `glDeleteProgram()`,
+    /// e.g. when handing the shader program to another library.
+    #[inline]
+    pub fn into_raw(self) -> RawGLHandle {
+        let handle = RawGLHandle(self.handle);
+        mem::forget(self);
+        handle
+    }
 }
 
 impl GLHandle for Shader<'_> {
@@ -463,6 +787,10 @@ fn get_program_info_log(handle: u32) -> Option<String> {
 pub enum ShaderStageError {
     #[error("compiling {} shader stage [{0}] failed: {2}", .1.name())]
     Compile(RawGLHandle, ShaderStageKind, Cow<'static, str>),
+    #[error("resolving shader include {0:?} failed")]
+    Include(String),
+    #[error("resolving shader include {0:?} exceeded the maximum include depth")]
+    IncludeDepth(String),
 }
 
 #[derive(Error, Debug)]
@@ -471,4 +799,17 @@ pub enum ShaderError {
     Link(RawGLHandle, Cow<'static, str>),
     #[error("validating shader program [{0}] failed: {1}")]
     Validation(RawGLHandle, Cow<'static, str>),
+    #[error("loading shader program [{0}] from binary failed: {1}")]
+    Binary(RawGLHandle, Cow<'static, str>),
+    #[error("attribute name {0:?} contains a nul byte")]
+    AttribName(String),
+}
+
+/// Error returned by [`Shader::new_from_combined()`].
+#[derive(Error, Debug)]
+pub enum CombinedShaderError {
+    #[error(transparent)]
+    ShaderStage(#[from] ShaderStageError),
+    #[error(transparent)]
+    Shader(#[from] ShaderError),
 }
@@ -0,0 +1,261 @@
+pub mod prelude {
+    pub use super::{SpriteBatch, SpriteBatchError, SpriteBatchPass};
+}
+
+use std::fmt;
+use std::ptr;
+
+use thiserror::Error;
+
+use super::{
+    AttribBindPoint, AttribBinding, AttribKind, AttribLayout, BlendFactor, Buffer, BufferUsage,
+    RenderingContext, SetUniform, Shader, ShaderError, ShaderStage, ShaderStageError, Texture,
+    UniformLocation, VertexArray, VertexArrayDesc,
+};
+
+const VERTEX_SHADER: &str = r#"#version 450 core
+
+layout(location = 0) uniform vec2 viewport_size;
+
+layout(location = 0) in vec2 in_pos;
+layout(location = 1) in vec2 in_uv;
+layout(location = 2) in vec4 in_color;
+
+out vec2 v_uv;
+out vec4 v_color;
+
+void main() {
+    vec2 ndc = (in_pos / viewport_size) * 2.0 - 1.0;
+    gl_Position = vec4(ndc.x, -ndc.y, 0.0, 1.0);
+    v_uv = in_uv;
+    v_color = in_color;
+}
+"#;
+
+const FRAGMENT_SHADER: &str = r#"#version 450 core
+
+layout(binding = 0) uniform sampler2D tex;
+
+in vec2 v_uv;
+in vec4 v_color;
+
+out vec4 out_color;
+
+void main() {
+    out_color = texture(tex, v_uv) * v_color;
+}
+"#;
+
+const VIEWPORT_SIZE_LOCATION: UniformLocation = UniformLocation(0);
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct SpriteVertex {
+    pos: [f32; 2],
+    uv: [f32; 2],
+    color: [f32; 4],
+}
+
+/// Renderer of textured quads, which accumulates sprites into a
+/// single buffer, and draws each run of sprites sharing the same
+/// texture in a single draw call.
+///
+/// ```no_run
+/// # use ren::prelude::*;
+/// # fn f<'gl>(
+/// #     ctx: &mut RenderingContext<'gl>,
+/// #     wnd: &Window,
+/// #     player: &Texture<'gl>,
+/// #     enemy: &Texture<'gl>,
+/// # ) -> Result<(), SpriteBatchError> {
+/// let mut batch = SpriteBatch::new(ctx)?;
+///
+/// let (width, height) = wnd.get_framebuffer_size();
+/// let mut pass = batch.begin((width as u32, height as u32));
+/// pass.draw(player, (10.0, 10.0, 32.0, 32.0), (1.0, 1.0, 1.0, 1.0));
+/// pass.draw(enemy, (50.0, 10.0, 32.0, 32.0), (1.0, 0.0, 0.0, 1.0));
+/// pass.end(ctx);
+/// # Ok(())
+/// # }
+/// ```
+pub struct SpriteBatch<'gl> {
+    shader: Shader<'gl>,
+    vao: VertexArray<'gl>,
+    buffer: Buffer<'gl>,
+    vertices: Vec<SpriteVertex>,
+}
+
+impl<'gl> SpriteBatch<'gl> {
+    pub fn new(ctx: &mut RenderingContext<'gl>) -> Result<Self, SpriteBatchError> {
+        let shader = {
+            let vs = ShaderStage::new_vertex(ctx, VERTEX_SHADER)?;
+            let fs = ShaderStage::new_fragment(ctx, FRAGMENT_SHADER)?;
+            Shader::new(ctx, &[vs, fs])?
+        };
+
+        let buffer = Buffer::new(ctx);
+
+        let layout = AttribLayout::from_kinds(&[
+            AttribKind::Float2, // Position
+            AttribKind::Float2, // Texture coordinates
+            AttribKind::Float4, // Color
+        ]);
+        let mut desc = VertexArrayDesc::new()
+            .with_buffer(&buffer)
+            .with_bind_point(AttribBindPoint::new(0, 0, layout.stride()));
+        for &attrib in layout.attribs() {
+            desc = desc
+                .with_binding(AttribBinding::new(attrib.index, 0))
+                .with_attrib(attrib);
+        }
+        let vao = VertexArray::new(ctx, desc);
+
+        Ok(Self {
+            shader,
+            vao,
+            buffer,
+            vertices: Vec::new(),
+        })
+    }
+
+    /// Begin submitting sprites, where coordinates are in pixels
+    /// with the origin at the top-left corner of `viewport_size`.
+    #[inline]
+    pub fn begin<'b, 't>(&'b mut self, viewport_size: (u32, u32)) -> SpriteBatchPass<'b, 't, 'gl> {
+        self.vertices.clear();
+        SpriteBatchPass {
+            batch: self,
+            viewport_size,
+            runs: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Debug for SpriteBatch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpriteBatch")
+            .field("shader", &self.shader)
+            .field("vao", &self.vao)
+            .field("buffer", &self.buffer)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sprites submitted to a [`SpriteBatch`], which are drawn when
+/// calling [`end()`](Self::end). Sprites are drawn in the order
+/// they are submitted, so to minimize draw calls, submit sprites
+/// sharing a texture consecutively, e.g. from a texture atlas.
+pub struct SpriteBatchPass<'b, 't, 'gl> {
+    batch: &'b mut SpriteBatch<'gl>,
+    viewport_size: (u32, u32),
+    /// Texture and vertex count of each run of sprites.
+    runs: Vec<(&'t Texture<'gl>, u32)>,
+}
+
+impl<'t, 'gl> SpriteBatchPass<'_, 't, 'gl> {
+    /// Draw the whole `texture` to the `(x, y, width, height)`
+    /// rectangle `dst`, multiplied by `color`.
+    #[inline]
+    pub fn draw(
+        &mut self,
+        texture: &'t Texture<'gl>,
+        dst: (f32, f32, f32, f32),
+        color: (f32, f32, f32, f32),
+    ) {
+        self.draw_region(texture, dst, (0.0, 0.0, 1.0, 1.0), color);
+    }
+
+    /// Draw the `(u0, v0, u1, v1)` region `uv` of `texture` to the
+    /// `(x, y, width, height)` rectangle `dst`, multiplied by `color`,
+    /// e.g. a single sprite of a texture atlas.
+    pub fn draw_region(
+        &mut self,
+        texture: &'t Texture<'gl>,
+        (x, y, w, h): (f32, f32, f32, f32),
+        (u0, v0, u1, v1): (f32, f32, f32, f32),
+        (r, g, b, a): (f32, f32, f32, f32),
+    ) {
+        let color = [r, g, b, a];
+        let vertex = |pos, uv| SpriteVertex { pos, uv, color };
+
+        let top_left = vertex([x, y], [u0, v0]);
+        let top_right = vertex([x + w, y], [u1, v0]);
+        let bottom_left = vertex([x, y + h], [u0, v1]);
+        let bottom_right = vertex([x + w, y + h], [u1, v1]);
+
+        #[rustfmt::skip]
+        self.batch.vertices.extend_from_slice(&[
+            top_left, bottom_left, bottom_right,
+            top_left, bottom_right, top_right,
+        ]);
+
+        match self.runs.last_mut() {
+            Some((last, count)) if ptr::eq(*last, texture) => {
+                *count += 6;
+            }
+            _ => self.runs.push((texture, 6)),
+        }
+    }
+
+    /// Draw all submitted sprites.
+    ///
+    /// This binds the vertex array, shader, and texture unit `0`,
+    /// and enables alpha blending. Other state, e.g. the depth test,
+    /// is left as is.
+    pub fn end(self, ctx: &mut RenderingContext<'gl>) {
+        let batch = self.batch;
+        if batch.vertices.is_empty() {
+            return;
+        }
+
+        batch.buffer.write(BufferUsage::Stream, &batch.vertices);
+
+        let (w, h) = self.viewport_size;
+        batch
+            .shader
+            .set_uniform(VIEWPORT_SIZE_LOCATION, (w as f32, h as f32));
+
+        ctx.set_blend_func(Some((BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha)));
+
+        let vao = ctx.bind_vertex_array(&batch.vao);
+        let _shader = ctx.bind_shader(&batch.shader);
+
+        let mut first = 0;
+        for (texture, count) in self.runs {
+            let _texture = ctx.bind_texture(texture, 0);
+            unsafe {
+                vao.draw_triangles(first / 3, count / 3);
+            }
+            first += count;
+        }
+    }
+
+    /// Returns the number of sprites submitted so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.batch.vertices.len() / 6
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.batch.vertices.is_empty()
+    }
+}
+
+impl fmt::Debug for SpriteBatchPass<'_, '_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpriteBatchPass")
+            .field("viewport_size", &self.viewport_size)
+            .field("sprites", &self.len())
+            .field("draw_calls", &self.runs.len())
+            .finish()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SpriteBatchError {
+    #[error("creating sprite batch shader stage failed: {0}")]
+    ShaderStage(#[from] ShaderStageError),
+    #[error("creating sprite batch shader failed: {0}")]
+    Shader(#[from] ShaderError),
+}
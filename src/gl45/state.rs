@@ -0,0 +1,124 @@
+pub mod prelude {
+    pub use super::{BlendFactor, ClipDepthMode, ClipOrigin, CullFace, DepthFunc, FrontFace};
+}
+
+/// Origin of the window coordinates, see
+/// [`RenderingContext::set_clip_control()`](super::RenderingContext::set_clip_control).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum ClipOrigin {
+    /// OpenGL convention, which is the default.
+    LowerLeft = gl::LOWER_LEFT,
+    /// Direct3D convention.
+    UpperLeft = gl::UPPER_LEFT,
+}
+
+/// Clip space depth range, see
+/// [`RenderingContext::set_clip_control()`](super::RenderingContext::set_clip_control).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum ClipDepthMode {
+    /// OpenGL convention `[-1, 1]`, which is the default.
+    NegativeOneToOne = gl::NEGATIVE_ONE_TO_ONE,
+    /// Direct3D convention `[0, 1]`, which gives better depth precision.
+    ZeroToOne = gl::ZERO_TO_ONE,
+}
+
+/// Comparison used by the depth test, see
+/// [`RenderingContext::set_depth_func()`](super::RenderingContext::set_depth_func).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum DepthFunc {
+    Never = gl::NEVER,
+    /// The default.
+    Less = gl::LESS,
+    Equal = gl::EQUAL,
+    LessEqual = gl::LEQUAL,
+    Greater = gl::GREATER,
+    NotEqual = gl::NOTEQUAL,
+    GreaterEqual = gl::GEQUAL,
+    Always = gl::ALWAYS,
+}
+
+/// Winding order of front-facing polygons, see
+/// [`RenderingContext::set_front_face()`](super::RenderingContext::set_front_face).
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+#[repr(u32)]
+pub enum FrontFace {
+    /// The default.
+    #[default]
+    CounterClockwise = gl::CCW,
+    Clockwise = gl::CW,
+}
+
+/// Polygons discarded by face culling, see
+/// [`RenderingContext::set_cull_face()`](super::RenderingContext::set_cull_face).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum CullFace {
+    Front = gl::FRONT,
+    Back = gl::BACK,
+    FrontAndBack = gl::FRONT_AND_BACK,
+}
+
+/// Factor of the source or destination color when blending, see
+/// [`RenderingContext::set_blend_func()`](super::RenderingContext::set_blend_func).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum BlendFactor {
+    Zero = gl::ZERO,
+    One = gl::ONE,
+    SrcColor = gl::SRC_COLOR,
+    OneMinusSrcColor = gl::ONE_MINUS_SRC_COLOR,
+    DstColor = gl::DST_COLOR,
+    OneMinusDstColor = gl::ONE_MINUS_DST_COLOR,
+    SrcAlpha = gl::SRC_ALPHA,
+    OneMinusSrcAlpha = gl::ONE_MINUS_SRC_ALPHA,
+    DstAlpha = gl::DST_ALPHA,
+    OneMinusDstAlpha = gl::ONE_MINUS_DST_ALPHA,
+}
+
+/// Cache of the last state set through [`RenderingContext`],
+/// used to skip redundant OpenGL state changes.
+///
+/// A `None` value means the state is unknown, e.g. initially
+/// or after [`RenderingContext::invalidate_state_cache()`],
+/// in which case the next setter always issues the OpenGL call.
+///
+/// [`RenderingContext`]: super::RenderingContext
+/// [`RenderingContext::invalidate_state_cache()`]: super::RenderingContext::invalidate_state_cache
+#[derive(Clone, Default, Debug)]
+pub(crate) struct StateCache {
+    pub(crate) clear_color: Option<(f32, f32, f32, f32)>,
+    pub(crate) clear_depth: Option<f32>,
+    pub(crate) clear_stencil: Option<i32>,
+    pub(crate) depth_test: Option<bool>,
+    pub(crate) depth_func: Option<DepthFunc>,
+    pub(crate) scissor_test: Option<bool>,
+    pub(crate) primitive_restart: Option<Option<u32>>,
+    pub(crate) color_mask: Option<(bool, bool, bool, bool)>,
+    pub(crate) depth_mask: Option<bool>,
+    pub(crate) blend_func: Option<Option<(BlendFactor, BlendFactor)>>,
+    pub(crate) polygon_offset: Option<Option<(f32, f32)>>,
+    pub(crate) min_sample_shading: Option<Option<f32>>,
+    pub(crate) alpha_to_coverage: Option<bool>,
+    pub(crate) line_width: Option<f32>,
+    pub(crate) point_size: Option<f32>,
+    pub(crate) clip_control: Option<(ClipOrigin, ClipDepthMode)>,
+    pub(crate) front_face: Option<FrontFace>,
+    pub(crate) cull_face: Option<Option<CullFace>>,
+}
+
+impl StateCache {
+    /// Returns `true` if `value` differs from the cached value,
+    /// i.e. the OpenGL call must be issued, and updates the cache.
+    #[inline]
+    pub(crate) fn update<T: PartialEq>(slot: &mut Option<T>, value: T) -> bool {
+        if slot.as_ref() == Some(&value) {
+            false
+        } else {
+            *slot = Some(value);
+            true
+        }
+    }
+}
@@ -0,0 +1,282 @@
+pub mod prelude {
+    pub use super::Std140Builder;
+}
+
+/// Builder of uniform block data laid out according to the `std140`
+/// rules, i.e. with the padding a `layout(std140)` block expects, e.g.
+/// `vec3` aligned to 16 bytes, and array elements padded to 16 bytes.
+///
+/// Fields must be appended in the order they are declared in GLSL:
+///
+/// ```no_run
+/// # use ren::prelude::*;
+/// # fn f(buffer: &mut Buffer<'_>) {
+/// // layout(std140, binding = 0) uniform Light {
+/// //     vec3 position;
+/// //     float intensity;
+/// //     vec3 color;
+/// //     float weights[4];
+/// // };
+/// let mut data = Std140Builder::new();
+/// data.vec3([0.0, 10.0, 0.0])
+///     .float(2.5)
+///     .vec3([1.0, 1.0, 1.0])
+///     .float_array(&[0.1, 0.2, 0.3, 0.4]);
+///
+/// buffer.write(BufferUsage::Dynamic, data.as_bytes());
+/// # }
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct Std140Builder {
+    /// Always padded to a multiple of 16 bytes, see [`Std140Builder::as_bytes()`].
+    data: Vec<u8>,
+    /// End of the last appended field, where the next field is aligned from.
+    end: usize,
+}
+
+impl Std140Builder {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            end: 0,
+        }
+    }
+
+    #[inline]
+    pub fn float(&mut self, value: f32) -> &mut Self {
+        self.push(4, &[value.to_ne_bytes()])
+    }
+
+    #[inline]
+    pub fn int(&mut self, value: i32) -> &mut Self {
+        self.push(4, &[value.to_ne_bytes()])
+    }
+
+    #[inline]
+    pub fn uint(&mut self, value: u32) -> &mut Self {
+        self.push(4, &[value.to_ne_bytes()])
+    }
+
+    /// GLSL `bool`, which is 4 bytes.
+    #[inline]
+    pub fn bool(&mut self, value: bool) -> &mut Self {
+        self.uint(value as u32)
+    }
+
+    #[inline]
+    pub fn vec2(&mut self, [x, y]: [f32; 2]) -> &mut Self {
+        self.push(8, &[x.to_ne_bytes(), y.to_ne_bytes()])
+    }
+
+    /// Aligned to 16 bytes, while only occupying 12 bytes,
+    /// i.e. a following scalar fills the remaining 4 bytes.
+    #[inline]
+    pub fn vec3(&mut self, [x, y, z]: [f32; 3]) -> &mut Self {
+        self.push(16, &[x.to_ne_bytes(), y.to_ne_bytes(), z.to_ne_bytes()])
+    }
+
+    #[inline]
+    pub fn vec4(&mut self, [x, y, z, w]: [f32; 4]) -> &mut Self {
+        self.push(
+            16,
+            &[
+                x.to_ne_bytes(),
+                y.to_ne_bytes(),
+                z.to_ne_bytes(),
+                w.to_ne_bytes(),
+            ],
+        )
+    }
+
+    /// Column-major `mat3`, where each column is padded to a `vec4`.
+    pub fn mat3(&mut self, columns: &[[f32; 3]; 3]) -> &mut Self {
+        for &[x, y, z] in columns {
+            self.vec4([x, y, z, 0.0]);
+        }
+        self
+    }
+
+    /// Column-major `mat4`, e.g. from `Mat4::to_cols_array()`.
+    pub fn mat4(&mut self, m: &[f32; 16]) -> &mut Self {
+        for column in m.chunks_exact(4) {
+            self.vec4([column[0], column[1], column[2], column[3]]);
+        }
+        self
+    }
+
+    /// `float[N]` array, where each element is padded to 16 bytes.
+    pub fn float_array(&mut self, values: &[f32]) -> &mut Self {
+        for &value in values {
+            self.vec4([value, 0.0, 0.0, 0.0]);
+        }
+        self
+    }
+
+    /// `vec4[N]` array, which requires no padding.
+    pub fn vec4_array(&mut self, values: &[[f32; 4]]) -> &mut Self {
+        for &value in values {
+            self.vec4(value);
+        }
+        self
+    }
+
+    /// Pad to a multiple of 16 bytes, i.e. the alignment of a nested struct,
+    /// which must be called both before and after appending its fields.
+    #[inline]
+    pub fn align_struct(&mut self) -> &mut Self {
+        self.align(16);
+        self
+    }
+
+    fn push(&mut self, align: usize, components: &[[u8; 4]]) -> &mut Self {
+        self.align(align);
+        self.data.truncate(self.end);
+        for component in components {
+            self.data.extend_from_slice(component);
+        }
+        self.end = self.data.len();
+        self.data.resize(self.end.div_ceil(16) * 16, 0);
+        self
+    }
+
+    #[inline]
+    fn align(&mut self, align: usize) {
+        self.end = self.end.div_ceil(align) * align;
+        self.data.resize(self.data.len().max(self.end), 0);
+    }
+
+    /// Returns the data laid out so far, which can be written to a
+    /// [`Buffer`](super::Buffer) bound as a uniform buffer.
+    ///
+    /// The data is padded to a multiple of 16 bytes, as some drivers
+    /// report a `GL_UNIFORM_BLOCK_DATA_SIZE` rounded up to 16 bytes,
+    /// which the bound range must cover.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Returns the size in bytes of the data laid out so far,
+    /// including the padding to a multiple of 16 bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.end = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Std140Builder;
+
+    fn floats(data: &Std140Builder) -> Vec<f32> {
+        data.as_bytes()
+            .chunks_exact(4)
+            .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+            .collect()
+    }
+
+    #[test]
+    fn light_example() {
+        let mut data = Std140Builder::new();
+        data.vec3([1.0, 2.0, 3.0])
+            .float(4.0)
+            .vec3([5.0, 6.0, 7.0])
+            .float_array(&[8.0, 9.0, 10.0, 11.0]);
+
+        // position at 0, intensity at 12, color at 16,
+        // and weights at 32 with a stride of 16
+        assert_eq!(data.len(), 96);
+        #[rustfmt::skip]
+        assert_eq!(
+            floats(&data),
+            [
+                1.0, 2.0, 3.0, 4.0,
+                5.0, 6.0, 7.0, 0.0,
+                8.0, 0.0, 0.0, 0.0,
+                9.0, 0.0, 0.0, 0.0,
+                10.0, 0.0, 0.0, 0.0,
+                11.0, 0.0, 0.0, 0.0,
+            ]
+        );
+    }
+
+    #[test]
+    fn vec2_alignment() {
+        let mut data = Std140Builder::new();
+        data.float(1.0).vec2([2.0, 3.0]).float(4.0);
+
+        // vec2 at 8, followed by the float at 16
+        assert_eq!(data.len(), 32);
+        assert_eq!(floats(&data)[..5], [1.0, 0.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn mat3_column_padding() {
+        let mut data = Std140Builder::new();
+        data.mat3(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]])
+            .float(10.0);
+
+        assert_eq!(data.len(), 64);
+        #[rustfmt::skip]
+        assert_eq!(
+            floats(&data),
+            [
+                1.0, 2.0, 3.0, 0.0,
+                4.0, 5.0, 6.0, 0.0,
+                7.0, 8.0, 9.0, 0.0,
+                10.0, 0.0, 0.0, 0.0,
+            ]
+        );
+    }
+
+    #[test]
+    fn align_struct() {
+        let mut data = Std140Builder::new();
+        data.float(1.0)
+            .align_struct()
+            .float(2.0)
+            .align_struct()
+            .float(3.0);
+
+        // The struct starts at 16, and the float after it at 32
+        assert_eq!(data.len(), 48);
+        assert_eq!(
+            floats(&data)[..9],
+            [1.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 3.0]
+        );
+    }
+
+    #[test]
+    fn padded_to_16_bytes() {
+        let mut data = Std140Builder::new();
+        assert!(data.is_empty());
+
+        data.float(1.0);
+        assert_eq!(data.len(), 16);
+
+        // Padding is filled by following fields
+        data.float(2.0).float(3.0);
+        assert_eq!(data.len(), 16);
+        assert_eq!(floats(&data), [1.0, 2.0, 3.0, 0.0]);
+
+        data.clear();
+        assert!(data.is_empty());
+    }
+}
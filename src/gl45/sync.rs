@@ -0,0 +1,129 @@
+pub mod prelude {
+    pub use super::{Fence, MemoryBarrier};
+}
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::BitOr;
+use std::ptr;
+use std::time::Duration;
+
+use gl::types::GLsync;
+
+use super::RenderingContext;
+
+/// Kinds of memory accesses, that must observe prior incoherent writes,
+/// which can be combined, e.g. <code>[MemoryBarrier::SHADER_STORAGE] |
+/// [MemoryBarrier::VERTEX_ATTRIB_ARRAY]</code>, see
+/// [`RenderingContext::memory_barrier()`].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct MemoryBarrier(u32);
+
+impl MemoryBarrier {
+    pub const VERTEX_ATTRIB_ARRAY: Self = Self(gl::VERTEX_ATTRIB_ARRAY_BARRIER_BIT);
+    pub const ELEMENT_ARRAY: Self = Self(gl::ELEMENT_ARRAY_BARRIER_BIT);
+    pub const UNIFORM: Self = Self(gl::UNIFORM_BARRIER_BIT);
+    pub const TEXTURE_FETCH: Self = Self(gl::TEXTURE_FETCH_BARRIER_BIT);
+    pub const SHADER_IMAGE_ACCESS: Self = Self(gl::SHADER_IMAGE_ACCESS_BARRIER_BIT);
+    pub const COMMAND: Self = Self(gl::COMMAND_BARRIER_BIT);
+    pub const PIXEL_BUFFER: Self = Self(gl::PIXEL_BUFFER_BARRIER_BIT);
+    pub const TEXTURE_UPDATE: Self = Self(gl::TEXTURE_UPDATE_BARRIER_BIT);
+    pub const BUFFER_UPDATE: Self = Self(gl::BUFFER_UPDATE_BARRIER_BIT);
+    pub const FRAMEBUFFER: Self = Self(gl::FRAMEBUFFER_BARRIER_BIT);
+    pub const TRANSFORM_FEEDBACK: Self = Self(gl::TRANSFORM_FEEDBACK_BARRIER_BIT);
+    pub const ATOMIC_COUNTER: Self = Self(gl::ATOMIC_COUNTER_BARRIER_BIT);
+    pub const SHADER_STORAGE: Self = Self(gl::SHADER_STORAGE_BARRIER_BIT);
+    pub const CLIENT_MAPPED_BUFFER: Self = Self(gl::CLIENT_MAPPED_BUFFER_BARRIER_BIT);
+    pub const QUERY_BUFFER: Self = Self(gl::QUERY_BUFFER_BARRIER_BIT);
+    pub const ALL: Self = Self(gl::ALL_BARRIER_BITS);
+
+    #[inline]
+    pub(crate) const fn bits(self) -> u32 {
+        self.0
+    }
+}
+
+impl BitOr for MemoryBarrier {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Sync object, which becomes signaled when the GPU
+/// has completed all commands issued before it.
+pub struct Fence<'gl> {
+    sync: GLsync,
+    phantom: PhantomData<&'gl ()>,
+}
+
+impl Fence<'static> {
+    /// # Safety
+    ///
+    /// Must only be called on a thread where there is a current
+    /// OpenGL context. The returned `Fence` must only
+    /// exist, while the OpenGL context is valid.
+    #[inline]
+    pub unsafe fn new_unsafe() -> Self {
+        Self::create()
+    }
+}
+
+impl<'gl> Fence<'gl> {
+    /// Insert a fence after all previously issued commands.
+    #[inline]
+    pub fn new(_ctx: &mut RenderingContext<'gl>) -> Self {
+        Self::create()
+    }
+
+    pub(crate) fn create() -> Self {
+        let sync = unsafe { gl::FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0) };
+        debug_assert!(!sync.is_null(), "failed creating fence");
+        Self {
+            sync,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Block until the fence is signaled or `timeout` expires.
+    /// A `timeout` of [`Duration::MAX`] waits indefinitely.
+    ///
+    /// Returns `true` if the fence is signaled.
+    pub fn wait(&self, timeout: Duration) -> bool {
+        let timeout = u64::try_from(timeout.as_nanos()).unwrap_or(u64::MAX);
+        let status = unsafe { gl::ClientWaitSync(self.sync, gl::SYNC_FLUSH_COMMANDS_BIT, timeout) };
+        match status {
+            gl::ALREADY_SIGNALED | gl::CONDITION_SATISFIED => true,
+            gl::TIMEOUT_EXPIRED => false,
+            _ => {
+                debug_assert_ne!(status, gl::WAIT_FAILED, "waiting for fence failed");
+                false
+            }
+        }
+    }
+
+    /// Returns `true` if the fence is signaled, without blocking.
+    pub fn signaled(&self) -> bool {
+        let mut status = 0;
+        unsafe {
+            gl::GetSynciv(self.sync, gl::SYNC_STATUS, 1, ptr::null_mut(), &mut status);
+        }
+        status == (gl::SIGNALED as i32)
+    }
+}
+
+impl Drop for Fence<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteSync(self.sync);
+        }
+    }
+}
+
+impl fmt::Debug for Fence<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fence({:?})", self.sync)
+    }
+}
@@ -1,12 +1,19 @@
 pub mod prelude {
-    pub use super::{InternalFormat, PixelFormat, Texture, TextureFilter, TextureWrap};
+    pub use super::{
+        BoundTextures, ImageAccess, InternalFormat, MultisampleTexture, PixelFormat, PixelType,
+        Swizzle, Texture, TextureFilter, TextureLevelInfo, TextureUnit, TextureView, TextureWrap,
+    };
 }
 
 use std::ffi::c_void;
 use std::fmt;
 use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, DerefMut};
 
-use super::{GLHandle, RenderingContext};
+use super::{
+    DepthFunc, GLHandle, RawGLHandle, RenderingContext, SetUniform, Shader, UniformLocation,
+};
 
 pub(super) unsafe fn init() {
     gl::PixelStorei(gl::UNPACK_ALIGNMENT, 1);
@@ -21,6 +28,46 @@ pub enum PixelFormat {
     Rgba = gl::RGBA,
 }
 
+impl PixelFormat {
+    /// Returns the number of components per pixel.
+    #[inline]
+    pub const fn components(self) -> usize {
+        match self {
+            Self::R => 1,
+            Self::Rg => 2,
+            Self::Rgb => 3,
+            Self::Rgba => 4,
+        }
+    }
+}
+
+/// Type of each component of uploaded pixels, see
+/// [`Texture::upload_image_data_typed()`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum PixelType {
+    UnsignedByte = gl::UNSIGNED_BYTE,
+    Byte = gl::BYTE,
+    UnsignedShort = gl::UNSIGNED_SHORT,
+    Short = gl::SHORT,
+    UnsignedInt = gl::UNSIGNED_INT,
+    Int = gl::INT,
+    HalfFloat = gl::HALF_FLOAT,
+    Float = gl::FLOAT,
+}
+
+impl PixelType {
+    /// Returns the size of a single component in bytes.
+    #[inline]
+    pub const fn size(self) -> usize {
+        match self {
+            Self::UnsignedByte | Self::Byte => 1,
+            Self::UnsignedShort | Self::Short | Self::HalfFloat => 2,
+            Self::UnsignedInt | Self::Int | Self::Float => 4,
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 #[repr(u32)]
 pub enum InternalFormat {
@@ -28,6 +75,62 @@ pub enum InternalFormat {
     Rg8 = gl::RG8,
     Rgb8 = gl::RGB8,
     Rgba8 = gl::RGBA8,
+    R16 = gl::R16,
+    Rg16 = gl::RG16,
+    Rgba16 = gl::RGBA16,
+    R16F = gl::R16F,
+    Rg16F = gl::RG16F,
+    Rgb16F = gl::RGB16F,
+    Rgba16F = gl::RGBA16F,
+    R32F = gl::R32F,
+    Rg32F = gl::RG32F,
+    Rgb32F = gl::RGB32F,
+    Rgba32F = gl::RGBA32F,
+    Depth16 = gl::DEPTH_COMPONENT16,
+    Depth24 = gl::DEPTH_COMPONENT24,
+    Depth32F = gl::DEPTH_COMPONENT32F,
+    Depth24Stencil8 = gl::DEPTH24_STENCIL8,
+    Depth32FStencil8 = gl::DEPTH32F_STENCIL8,
+    Stencil8 = gl::STENCIL_INDEX8,
+    /// BC1 (DXT1), from `EXT_texture_compression_s3tc`.
+    Bc1Rgb = 0x83F0,
+    /// BC1 (DXT1) with 1-bit alpha, from `EXT_texture_compression_s3tc`.
+    Bc1Rgba = 0x83F1,
+    /// BC2 (DXT3), from `EXT_texture_compression_s3tc`.
+    Bc2 = 0x83F2,
+    /// BC3 (DXT5), from `EXT_texture_compression_s3tc`.
+    Bc3 = 0x83F3,
+    Bc4 = gl::COMPRESSED_RED_RGTC1,
+    Bc5 = gl::COMPRESSED_RG_RGTC2,
+    Bc6hUnsigned = gl::COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT,
+    Bc6hSigned = gl::COMPRESSED_RGB_BPTC_SIGNED_FLOAT,
+    Bc7 = gl::COMPRESSED_RGBA_BPTC_UNORM,
+    Bc7Srgb = gl::COMPRESSED_SRGB_ALPHA_BPTC_UNORM,
+    Etc2Rgb8 = gl::COMPRESSED_RGB8_ETC2,
+    Etc2Rgba8 = gl::COMPRESSED_RGBA8_ETC2_EAC,
+}
+
+impl InternalFormat {
+    /// Returns `true` for block compressed formats, which must be
+    /// uploaded using [`Texture::upload_compressed()`].
+    #[inline]
+    pub const fn is_compressed(self) -> bool {
+        matches!(
+            self,
+            Self::Bc1Rgb
+                | Self::Bc1Rgba
+                | Self::Bc2
+                | Self::Bc3
+                | Self::Bc4
+                | Self::Bc5
+                | Self::Bc6hUnsigned
+                | Self::Bc6hSigned
+                | Self::Bc7
+                | Self::Bc7Srgb
+                | Self::Etc2Rgb8
+                | Self::Etc2Rgba8
+        )
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
@@ -47,9 +150,94 @@ pub enum TextureFilter {
     Linear = gl::LINEAR,
 }
 
+/// Source of a color component when sampling a texture,
+/// see [`Texture::set_swizzle()`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum Swizzle {
+    Red = gl::RED,
+    Green = gl::GREEN,
+    Blue = gl::BLUE,
+    Alpha = gl::ALPHA,
+    Zero = gl::ZERO,
+    One = gl::ONE,
+}
+
+/// Texture unit, e.g. handed out by
+/// [`RenderingContext::allocate_texture_unit()`], which can be used
+/// as the value of a sampler uniform.
+///
+/// ```no_run
+/// # use ren::prelude::*;
+/// # fn f<'gl>(
+/// #     ctx: &mut RenderingContext<'gl>,
+/// #     shader: &Shader<'gl>,
+/// #     texture: &Texture<'gl>,
+/// #     loc: UniformLocation,
+/// # ) {
+/// let unit = ctx.allocate_texture_unit().expect("out of texture units");
+/// let _texture = ctx.bind_texture(texture, unit.index());
+/// shader.set_uniform(loc, unit);
+/// # }
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct TextureUnit(u32);
+
+impl TextureUnit {
+    #[inline]
+    pub const fn new(index: u32) -> Self {
+        Self(index)
+    }
+
+    #[inline]
+    pub const fn index(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<TextureUnit> for u32 {
+    #[inline]
+    fn from(unit: TextureUnit) -> Self {
+        unit.0
+    }
+}
+
+impl SetUniform<TextureUnit> for Shader<'_> {
+    #[inline]
+    fn set_uniform(&self, loc: UniformLocation, unit: TextureUnit) {
+        self.set_uniform(loc, unit.0 as i32);
+    }
+}
+
+/// Access of a shader to an image unit, see [`Texture::bind_image()`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u32)]
+pub enum ImageAccess {
+    Read = gl::READ_ONLY,
+    Write = gl::WRITE_ONLY,
+    ReadWrite = gl::READ_WRITE,
+}
+
+/// Properties of a single mip level, as reported by OpenGL,
+/// see [`Texture::level_info()`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct TextureLevelInfo {
+    pub size: (u32, u32),
+    /// Raw `GL_TEXTURE_INTERNAL_FORMAT`, which for textures not created
+    /// by [`Texture`] might not be any of [`InternalFormat`].
+    pub internal_format: u32,
+    pub compressed: bool,
+    /// Size of the compressed image in bytes, or `0` if not compressed.
+    pub compressed_size: usize,
+}
+
 pub struct Texture<'gl> {
     handle: u32,
     size: (u32, u32),
+    levels: u32,
+    /// Whether the handle is deleted on drop,
+    /// see [`Texture::from_raw_handle()`].
+    owned: bool,
     phantom: PhantomData<&'gl ()>,
 }
 
@@ -61,7 +249,7 @@ impl Texture<'static> {
     /// exist, while the OpenGL context is valid.
     #[inline]
     pub unsafe fn new_unsafe(size: (u32, u32), internal_format: InternalFormat) -> Self {
-        Self::create(size, internal_format)
+        Self::create(size, 1, internal_format)
     }
 }
 
@@ -72,10 +260,61 @@ impl<'gl> Texture<'gl> {
         size: (u32, u32),
         internal_format: InternalFormat,
     ) -> Self {
-        Self::create(size, internal_format)
+        Self::create(size, 1, internal_format)
     }
 
-    fn create(size: (u32, u32), internal_format: InternalFormat) -> Self {
+    /// Create a texture with storage for `levels` mip levels, e.g. to
+    /// upload a precomputed mip chain using [`upload_sub_image_data()`].
+    ///
+    /// [`upload_sub_image_data()`]: Self::upload_sub_image_data
+    #[inline]
+    pub fn new_with_levels(
+        _ctx: &mut RenderingContext<'gl>,
+        size: (u32, u32),
+        levels: u32,
+        internal_format: InternalFormat,
+    ) -> Self {
+        Self::create(size, levels, internal_format)
+    }
+
+    /// Adopt an existing 2D texture `handle`, e.g. created by another library.
+    /// The size and number of mip levels are queried from the texture.
+    ///
+    /// If `owned` is `true`, then the texture is deleted when the returned
+    /// `Texture` is dropped, i.e. ownership is transferred. Otherwise the
+    /// texture must be deleted by its creator, after the returned `Texture`
+    /// is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The `handle` must be a valid texture with a target of `GL_TEXTURE_2D`,
+    /// created in the same OpenGL context as `ctx`, or a shared context.
+    /// If `owned` is `true`, then the texture must not be deleted by anything else.
+    pub unsafe fn from_raw_handle(
+        _ctx: &mut RenderingContext<'gl>,
+        handle: RawGLHandle,
+        owned: bool,
+    ) -> Self {
+        let handle = handle.gl_handle();
+
+        let (mut width, mut height, mut levels) = (0, 0, 0);
+        gl::GetTextureLevelParameteriv(handle, 0, gl::TEXTURE_WIDTH, &mut width);
+        gl::GetTextureLevelParameteriv(handle, 0, gl::TEXTURE_HEIGHT, &mut height);
+        gl::GetTextureParameteriv(handle, gl::TEXTURE_IMMUTABLE_LEVELS, &mut levels);
+
+        Self {
+            handle,
+            size: (width as u32, height as u32),
+            // Mutable textures report 0 levels
+            levels: (levels as u32).max(1),
+            owned,
+            phantom: PhantomData,
+        }
+    }
+
+    fn create(size: (u32, u32), levels: u32, internal_format: InternalFormat) -> Self {
+        debug_assert!(levels > 0);
+
         let mut tex = {
             let mut handle = 0;
             unsafe {
@@ -86,6 +325,8 @@ impl<'gl> Texture<'gl> {
             Self {
                 handle,
                 size,
+                levels,
+                owned: true,
                 phantom: PhantomData,
             }
         };
@@ -93,7 +334,7 @@ impl<'gl> Texture<'gl> {
         unsafe {
             gl::TextureStorage2D(
                 tex.handle,
-                1,
+                levels as i32,
                 internal_format as u32,
                 tex.size.0 as i32,
                 tex.size.1 as i32,
@@ -104,11 +345,70 @@ impl<'gl> Texture<'gl> {
         tex.set_filter(TextureFilter::default());
 
         tex.set_parameter(gl::TEXTURE_BASE_LEVEL, 0);
-        tex.set_parameter(gl::TEXTURE_MAX_LEVEL, 0);
+        tex.set_parameter(gl::TEXTURE_MAX_LEVEL, (levels - 1) as i32);
 
         tex
     }
 
+    /// Create a view sharing the storage of this texture, reinterpreting
+    /// it as `internal_format`, and exposing `num_levels` mip levels starting
+    /// at `min_level`, e.g. to sample a single mip level.
+    ///
+    /// The `internal_format` must be in the same view class as the format
+    /// of this texture, i.e. have the same size per texel, and a color
+    /// format cannot be viewed as a depth format.
+    pub fn create_view(
+        &self,
+        internal_format: InternalFormat,
+        min_level: u32,
+        num_levels: u32,
+    ) -> TextureView<'_, 'gl> {
+        debug_assert!(num_levels > 0);
+        debug_assert!(min_level + num_levels <= self.levels);
+
+        let mut tex = {
+            let mut handle = 0;
+            unsafe {
+                // `gl::TextureView()` requires a name which
+                // has not been initialized, i.e. not created
+                gl::GenTextures(1, &mut handle);
+            }
+            debug_assert_ne!(handle, 0, "failed creating texture view");
+            Self {
+                handle,
+                size: self.level_size(min_level),
+                levels: num_levels,
+                owned: true,
+                phantom: PhantomData,
+            }
+        };
+
+        unsafe {
+            gl::TextureView(
+                tex.handle,
+                gl::TEXTURE_2D,
+                self.handle,
+                internal_format as u32,
+                min_level,
+                num_levels,
+                // 2D textures have a single layer
+                0,
+                1,
+            );
+        }
+
+        tex.set_wrap(TextureWrap::default());
+        tex.set_filter(TextureFilter::default());
+
+        tex.set_parameter(gl::TEXTURE_BASE_LEVEL, 0);
+        tex.set_parameter(gl::TEXTURE_MAX_LEVEL, (num_levels - 1) as i32);
+
+        TextureView {
+            texture: tex,
+            phantom: PhantomData,
+        }
+    }
+
     #[inline]
     pub fn upload_image_data(
         &mut self,
@@ -116,7 +416,7 @@ impl<'gl> Texture<'gl> {
         format: PixelFormat,
         pixels: impl AsRef<[u8]>,
     ) {
-        self.upload_sub_image_data((0, 0), (width, height), format, pixels);
+        self.upload_sub_image_data(0, (0, 0), (width, height), format, pixels);
     }
 
     #[inline]
@@ -126,11 +426,50 @@ impl<'gl> Texture<'gl> {
         format: PixelFormat,
         pixels: *const u8,
     ) {
-        self.upload_sub_image_data_from_ptr((0, 0), (width, height), format, pixels);
+        self.upload_sub_image_data_from_ptr(0, (0, 0), (width, height), format, pixels);
     }
 
+    /// Upload `pixels` to the whole of mip level 0, where each component is a
+    /// `T` of `ty`, e.g. `&[f32]` with [`PixelType::Float`] for HDR data.
+    ///
+    /// ```no_run
+    /// # use ren::prelude::*;
+    /// # fn f(ctx: &mut RenderingContext<'_>, (w, h): (u32, u32), hdr_pixels: &[f32]) {
+    /// let mut tex = ctx.create_texture((w, h), InternalFormat::Rgb32F);
+    /// tex.upload_image_data_typed((w, h), PixelFormat::Rgb, PixelType::Float, hdr_pixels);
+    /// # }
+    /// ```
+    pub fn upload_image_data_typed<T: Copy>(
+        &mut self,
+        (width, height): (u32, u32),
+        format: PixelFormat,
+        ty: PixelType,
+        pixels: &[T],
+    ) {
+        debug_assert_eq!(
+            mem::size_of::<T>(),
+            ty.size(),
+            "size of component does not match {:?}",
+            ty
+        );
+        debug_assert!(((width as usize) * (height as usize) * format.components()) <= pixels.len());
+
+        unsafe {
+            self.upload_sub_image(
+                0,
+                (0, 0),
+                (width, height),
+                format,
+                ty,
+                pixels.as_ptr() as *const c_void,
+            );
+        }
+    }
+
+    /// Upload `pixels` to the `(x, y, width, height)` region of mip `level`.
     pub fn upload_sub_image_data(
         &mut self,
+        level: u32,
         (x, y): (u32, u32),
         (width, height): (u32, u32),
         format: PixelFormat,
@@ -141,38 +480,93 @@ impl<'gl> Texture<'gl> {
         debug_assert!(((width as usize) * (height as usize)) <= pixels.len());
 
         unsafe {
-            self.upload_sub_image_data_from_ptr((x, y), (width, height), format, pixels.as_ptr());
+            self.upload_sub_image_data_from_ptr(
+                level,
+                (x, y),
+                (width, height),
+                format,
+                pixels.as_ptr(),
+            );
         }
     }
 
     pub unsafe fn upload_sub_image_data_from_ptr(
         &mut self,
+        level: u32,
         (x, y): (u32, u32),
         (width, height): (u32, u32),
         format: PixelFormat,
         pixels: *const u8,
+    ) {
+        self.upload_sub_image(
+            level,
+            (x, y),
+            (width, height),
+            format,
+            PixelType::UnsignedByte,
+            pixels as *const c_void,
+        );
+    }
+
+    unsafe fn upload_sub_image(
+        &mut self,
+        level: u32,
+        (x, y): (u32, u32),
+        (width, height): (u32, u32),
+        format: PixelFormat,
+        ty: PixelType,
+        pixels: *const c_void,
     ) {
         debug_assert!(x < (i32::MAX as u32));
         debug_assert!(y < (i32::MAX as u32));
         debug_assert!(width < (i32::MAX as u32));
         debug_assert!(height < (i32::MAX as u32));
 
-        debug_assert!(self.size.0 >= (x + width));
-        debug_assert!(self.size.1 >= (y + height));
+        debug_assert!(level < self.levels);
 
-        debug_assert!((self.size.0 * self.size.1) >= (width * height));
+        let (level_width, level_height) = self.level_size(level);
+        debug_assert!(level_width >= (x + width));
+        debug_assert!(level_height >= (y + height));
+
+        debug_assert!((level_width * level_height) >= (width * height));
 
         unsafe {
             gl::TextureSubImage2D(
                 self.handle,
-                0,
+                level as i32,
                 x as i32,
                 y as i32,
                 width as i32,
                 height as i32,
                 format as u32,
-                gl::UNSIGNED_BYTE,
-                pixels as *const c_void,
+                ty as u32,
+                pixels,
+            );
+        }
+    }
+
+    /// Upload the compressed blob `data` to the whole of mip `level`.
+    ///
+    /// The texture must have been created with the compressed `format`,
+    /// and `data` must contain every block of the level, e.g. as loaded
+    /// from a DDS or KTX file.
+    pub fn upload_compressed(&mut self, level: u32, format: InternalFormat, data: &[u8]) {
+        debug_assert!(format.is_compressed(), "{:?} is not compressed", format);
+        debug_assert!(level < self.levels);
+
+        let (width, height) = self.level_size(level);
+
+        unsafe {
+            gl::CompressedTextureSubImage2D(
+                self.handle,
+                level as i32,
+                0,
+                0,
+                width as i32,
+                height as i32,
+                format as u32,
+                data.len() as i32,
+                data.as_ptr() as *const c_void,
             );
         }
     }
@@ -199,6 +593,63 @@ impl<'gl> Texture<'gl> {
         self.set_parameter(gl::TEXTURE_MAG_FILTER, filter as i32);
     }
 
+    /// Set the source of each of the `(r, g, b, a)` components when
+    /// sampling, e.g. an `R8` font atlas can be sampled as white with
+    /// alpha using `set_swizzle(One, One, One, Red)`.
+    ///
+    /// By default `(Red, Green, Blue, Alpha)`.
+    pub fn set_swizzle(&mut self, r: Swizzle, g: Swizzle, b: Swizzle, a: Swizzle) {
+        let swizzle = [r as i32, g as i32, b as i32, a as i32];
+        unsafe {
+            gl::TextureParameteriv(self.handle, gl::TEXTURE_SWIZZLE_RGBA, swizzle.as_ptr());
+        }
+    }
+
+    /// Enable depth comparison using `func` when sampling, or disable it
+    /// if `None`, which is the default. This is required for sampling a
+    /// depth texture using a `sampler2DShadow`, which with
+    /// [`TextureFilter::Linear`] enables hardware PCF for shadow maps.
+    pub fn set_compare(&mut self, func: Option<DepthFunc>) {
+        match func {
+            Some(func) => {
+                self.set_parameter(gl::TEXTURE_COMPARE_MODE, gl::COMPARE_REF_TO_TEXTURE as i32);
+                self.set_parameter(gl::TEXTURE_COMPARE_FUNC, func as i32);
+            }
+            None => {
+                self.set_parameter(gl::TEXTURE_COMPARE_MODE, gl::NONE as i32);
+            }
+        }
+    }
+
+    /// Offset the level of detail used for selecting mip levels, i.e. a
+    /// negative `bias` samples sharper levels, and a positive `bias`
+    /// samples blurrier levels.
+    ///
+    /// By default `0.0`. This is only meaningful for textures with
+    /// multiple mip levels, see [`Texture::new_with_levels()`].
+    #[inline]
+    pub fn set_lod_bias(&mut self, bias: f32) {
+        self.set_parameter_f32(gl::TEXTURE_LOD_BIAS, bias);
+    }
+
+    /// Clamp the level of detail used for selecting mip levels to
+    /// `min..=max`, e.g. to avoid sampling the smallest levels.
+    ///
+    /// By default `-1000.0..=1000.0`, i.e. unclamped.
+    #[inline]
+    pub fn set_lod_range(&mut self, min: f32, max: f32) {
+        debug_assert!(min <= max);
+        self.set_parameter_f32(gl::TEXTURE_MIN_LOD, min);
+        self.set_parameter_f32(gl::TEXTURE_MAX_LOD, max);
+    }
+
+    #[inline]
+    fn set_parameter_f32(&mut self, name: u32, value: f32) {
+        unsafe {
+            gl::TextureParameterf(self.handle, name, value);
+        }
+    }
+
     #[inline]
     fn set_parameter(&mut self, name: u32, value: i32) {
         unsafe {
@@ -206,15 +657,177 @@ impl<'gl> Texture<'gl> {
         }
     }
 
+    /// Bind the texture to texture `unit`.
+    ///
+    /// # Safety
+    ///
+    /// The texture must be unbound or another texture bound to `unit`
+    /// before this texture is dropped.
     #[inline]
     pub unsafe fn bind(&self, unit: u32) {
         gl::BindTextureUnit(unit, self.handle);
     }
 
+    /// Bind mip `level` to image `unit`, for image load/store in shaders,
+    /// e.g. `layout(binding = 0, rgba8) uniform writeonly image2D img;`.
+    ///
+    /// If `layered` is `true` all layers are bound, otherwise only `layer`.
+    /// The `format` must match the format declared in the shader, and must
+    /// be compatible with the format of the texture.
+    ///
+    /// # Safety
+    ///
+    /// Another texture must be bound to image `unit` before this texture
+    /// is dropped, and accesses in shaders must be synchronized using
+    /// [`RenderingContext::memory_barrier()`].
+    #[inline]
+    pub unsafe fn bind_image(
+        &self,
+        unit: u32,
+        level: u32,
+        layered: bool,
+        layer: u32,
+        access: ImageAccess,
+        format: InternalFormat,
+    ) {
+        debug_assert!(level < self.levels);
+
+        gl::BindImageTexture(
+            unit,
+            self.handle,
+            level as i32,
+            if layered { gl::TRUE } else { gl::FALSE },
+            layer as i32,
+            access as u32,
+            format as u32,
+        );
+    }
+
     #[inline]
     pub fn size(&self) -> (u32, u32) {
         self.size
     }
+
+    /// Returns the size of mip `level`, i.e. the size halved `level` times.
+    #[inline]
+    pub fn level_size(&self, level: u32) -> (u32, u32) {
+        ((self.size.0 >> level).max(1), (self.size.1 >> level).max(1))
+    }
+
+    /// Returns the number of mip levels.
+    #[inline]
+    pub fn levels(&self) -> u32 {
+        self.levels
+    }
+
+    /// Query the properties of mip `level` from OpenGL, e.g. to
+    /// validate a texture adopted using [`Texture::from_raw_handle()`].
+    pub fn level_info(&self, level: u32) -> TextureLevelInfo {
+        debug_assert!(level < self.levels);
+
+        let param = |pname| {
+            let mut value = 0;
+            unsafe {
+                gl::GetTextureLevelParameteriv(self.handle, level as i32, pname, &mut value);
+            }
+            value
+        };
+
+        let compressed = param(gl::TEXTURE_COMPRESSED) != 0;
+        TextureLevelInfo {
+            size: (
+                param(gl::TEXTURE_WIDTH) as u32,
+                param(gl::TEXTURE_HEIGHT) as u32,
+            ),
+            internal_format: param(gl::TEXTURE_INTERNAL_FORMAT) as u32,
+            compressed,
+            compressed_size: if compressed {
+                param(gl::TEXTURE_COMPRESSED_IMAGE_SIZE) as usize
+            } else {
+                0
+            },
+        }
+    }
+
+    /// Copy the `size` region at `src_offset` of mip level 0 to
+    /// `dst_offset` of mip level 0 of `dst`, without a round-trip
+    /// through the CPU, e.g. when packing a texture atlas.
+    ///
+    /// The internal formats must be compatible, i.e. have the same
+    /// size per texel, unless either is compressed.
+    pub fn copy_to(
+        &self,
+        dst: &mut Texture<'gl>,
+        src_offset: (u32, u32),
+        dst_offset: (u32, u32),
+        size: (u32, u32),
+    ) {
+        debug_assert!(src_offset.0 + size.0 <= self.size.0 && src_offset.1 + size.1 <= self.size.1);
+        debug_assert!(dst_offset.0 + size.0 <= dst.size.0 && dst_offset.1 + size.1 <= dst.size.1);
+        if cfg!(debug_assertions) {
+            let (src_info, dst_info) = (self.level_info(0), dst.level_info(0));
+            if !src_info.compressed && !dst_info.compressed {
+                assert_eq!(
+                    self.texel_bits(0),
+                    dst.texel_bits(0),
+                    "incompatible internal formats {:#X} and {:#X}",
+                    src_info.internal_format,
+                    dst_info.internal_format,
+                );
+            }
+        }
+
+        unsafe {
+            gl::CopyImageSubData(
+                self.handle,
+                gl::TEXTURE_2D,
+                0,
+                src_offset.0 as i32,
+                src_offset.1 as i32,
+                0,
+                dst.handle,
+                gl::TEXTURE_2D,
+                0,
+                dst_offset.0 as i32,
+                dst_offset.1 as i32,
+                0,
+                size.0 as i32,
+                size.1 as i32,
+                1,
+            );
+        }
+    }
+
+    /// Returns the number of bits per texel of mip `level`.
+    fn texel_bits(&self, level: u32) -> i32 {
+        [
+            gl::TEXTURE_RED_SIZE,
+            gl::TEXTURE_GREEN_SIZE,
+            gl::TEXTURE_BLUE_SIZE,
+            gl::TEXTURE_ALPHA_SIZE,
+            gl::TEXTURE_DEPTH_SIZE,
+            gl::TEXTURE_STENCIL_SIZE,
+        ]
+        .into_iter()
+        .map(|pname| {
+            let mut bits = 0;
+            unsafe {
+                gl::GetTextureLevelParameteriv(self.handle, level as i32, pname, &mut bits);
+            }
+            bits
+        })
+        .sum()
+    }
+
+    /// Returns the handle without deleting the texture, i.e. the
+    /// caller becomes responsible for calling `glDeleteTextures()`,
+    /// e.g. when handing the texture to another library.
+    #[inline]
+    pub fn into_raw(self) -> RawGLHandle {
+        let handle = RawGLHandle(self.handle);
+        mem::forget(self);
+        handle
+    }
 }
 
 impl GLHandle for Texture<'_> {
@@ -226,8 +839,10 @@ impl GLHandle for Texture<'_> {
 
 impl Drop for Texture<'_> {
     fn drop(&mut self) {
-        unsafe {
-            gl::DeleteTextures(1, &self.handle);
+        if self.owned {
+            unsafe {
+                gl::DeleteTextures(1, &self.handle);
+            }
         }
     }
 }
@@ -237,3 +852,230 @@ impl fmt::Debug for Texture<'_> {
         write!(f, "Texture({}, {:?})", self.handle, self.size)
     }
 }
+
+/// Multisampled texture, i.e. `GL_TEXTURE_2D_MULTISAMPLE`, e.g. an MSAA
+/// render target, which can be attached to a [`Framebuffer`] using
+/// [`Framebuffer::attach_multisample_texture()`].
+///
+/// Unlike [`Texture`], a multisampled texture has no mip levels and
+/// no filtering, and cannot be uploaded to. Its contents are produced
+/// by rendering, and are resolved by blitting, or sampled per sample
+/// using a `sampler2DMS`.
+///
+/// [`Framebuffer`]: super::Framebuffer
+/// [`Framebuffer::attach_multisample_texture()`]: super::Framebuffer::attach_multisample_texture
+pub struct MultisampleTexture<'gl> {
+    handle: u32,
+    size: (u32, u32),
+    samples: u32,
+    phantom: PhantomData<&'gl ()>,
+}
+
+impl MultisampleTexture<'static> {
+    /// # Safety
+    ///
+    /// Must only be called on a thread where there is a current
+    /// OpenGL context. The returned `MultisampleTexture` must only
+    /// exist, while the OpenGL context is valid.
+    #[inline]
+    pub unsafe fn new_unsafe(
+        size: (u32, u32),
+        internal_format: InternalFormat,
+        samples: u32,
+    ) -> Self {
+        Self::create(size, internal_format, samples)
+    }
+}
+
+impl<'gl> MultisampleTexture<'gl> {
+    #[inline]
+    pub fn new(
+        _ctx: &mut RenderingContext<'gl>,
+        size: (u32, u32),
+        internal_format: InternalFormat,
+        samples: u32,
+    ) -> Self {
+        Self::create(size, internal_format, samples)
+    }
+
+    fn create(size: (u32, u32), internal_format: InternalFormat, samples: u32) -> Self {
+        debug_assert!(samples > 0);
+
+        let tex = {
+            let mut handle = 0;
+            unsafe {
+                gl::CreateTextures(gl::TEXTURE_2D_MULTISAMPLE, 1, &mut handle);
+            }
+            debug_assert_ne!(handle, 0, "failed creating multisample texture");
+            // Constructed early to ensure `gl::DeleteTextures()` is called on error
+            Self {
+                handle,
+                size,
+                samples,
+                phantom: PhantomData,
+            }
+        };
+
+        unsafe {
+            gl::TextureStorage2DMultisample(
+                tex.handle,
+                samples as i32,
+                internal_format as u32,
+                size.0 as i32,
+                size.1 as i32,
+                gl::TRUE,
+            );
+        }
+
+        tex
+    }
+
+    /// Bind the multisampled texture to texture `unit`, e.g. for
+    /// sampling it as a `sampler2DMS` using `texelFetch()`.
+    ///
+    /// # Safety
+    ///
+    /// Another texture must be bound to `unit` before this texture
+    /// is dropped.
+    #[inline]
+    pub unsafe fn bind(&self, unit: u32) {
+        gl::BindTextureUnit(unit, self.handle);
+    }
+
+    #[inline]
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    #[inline]
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+}
+
+impl GLHandle for MultisampleTexture<'_> {
+    #[inline]
+    unsafe fn gl_handle(&self) -> u32 {
+        self.handle
+    }
+}
+
+impl Drop for MultisampleTexture<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteTextures(1, &self.handle);
+        }
+    }
+}
+
+impl fmt::Debug for MultisampleTexture<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "MultisampleTexture({}, {:?}, {})",
+            self.handle, self.size, self.samples
+        )
+    }
+}
+
+/// [`Texture`] sharing the storage of another texture,
+/// see [`Texture::create_view()`].
+pub struct TextureView<'a, 'gl> {
+    texture: Texture<'gl>,
+    phantom: PhantomData<&'a Texture<'gl>>,
+}
+
+impl<'gl> Deref for TextureView<'_, 'gl> {
+    type Target = Texture<'gl>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.texture
+    }
+}
+
+impl<'gl> DerefMut for TextureView<'_, 'gl> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.texture
+    }
+}
+
+impl fmt::Debug for TextureView<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TextureView").field(&self.texture).finish()
+    }
+}
+
+/// Set of textures bound to texture units in one call,
+/// e.g. all the textures of a material.
+///
+/// ```no_run
+/// # use ren::prelude::*;
+/// # fn f<'gl>(shader: &Shader<'gl>, diffuse: &Texture<'gl>, normal: &Texture<'gl>) {
+/// let textures = BoundTextures::new()
+///     .with_sampler(diffuse, 0, shader.get_uniform_location("diffuse").unwrap())
+///     .with_sampler(normal, 1, shader.get_uniform_location("normal").unwrap());
+///
+/// unsafe {
+///     textures.bind(shader);
+/// }
+/// # }
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct BoundTextures<'gl, 'a> {
+    bindings: Vec<(&'a Texture<'gl>, u32, Option<UniformLocation>)>,
+}
+
+impl<'gl, 'a> BoundTextures<'gl, 'a> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Bind `texture` to `unit`, e.g. when the sampler
+    /// uses an explicit `layout(binding = unit)`.
+    #[inline]
+    pub fn with_texture(mut self, texture: &'a Texture<'gl>, unit: u32) -> Self {
+        self.bindings.push((texture, unit, None));
+        self
+    }
+
+    /// Bind `texture` to `unit`, and set the sampler uniform at `loc` to `unit`.
+    #[inline]
+    pub fn with_sampler(
+        mut self,
+        texture: &'a Texture<'gl>,
+        unit: u32,
+        loc: UniformLocation,
+    ) -> Self {
+        self.bindings.push((texture, unit, Some(loc)));
+        self
+    }
+
+    /// Bind all textures to their texture units, and set
+    /// the sampler uniforms of `shader` accordingly.
+    ///
+    /// # Safety
+    ///
+    /// The textures must be unbound or replaced before any of them
+    /// are dropped, see [`Texture::bind()`].
+    pub unsafe fn bind(&self, shader: &Shader<'gl>) {
+        self.set_sampler_uniforms(shader);
+        for &(texture, unit, _) in &self.bindings {
+            texture.bind(unit);
+        }
+    }
+
+    /// Set the sampler uniforms of `shader` to their texture
+    /// units, without binding the textures.
+    pub fn set_sampler_uniforms(&self, shader: &Shader<'gl>) {
+        for &(_, unit, loc) in &self.bindings {
+            if let Some(loc) = loc {
+                shader.set_uniform(loc, unit as i32);
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 pub mod prelude {
-    pub use super::{SetUniform, UniformLocation};
+    pub use super::{SetUniform, UniformComponents, UniformLocation};
 }
 
 use std::ffi::{c_char, CStr, CString};
@@ -15,26 +15,17 @@ use super::{GLHandle, Shader};
 pub struct UniformLocation(pub(crate) u32);
 
 impl UniformLocation {
-    /// Returns `None` if `name` does not correspond to an active uniform variable.
-    ///
-    /// Panics if `name` contains a nul byte.
+    /// Returns `None` if `name` does not correspond to an active uniform variable,
+    /// including if `name` contains a nul byte.
     #[inline]
     pub(crate) fn get_uniform_location(
         program: u32,
         name: impl AsRef<str>,
     ) -> Option<UniformLocation> {
-        let name = name.as_ref();
-
         // Technically, a nul byte is valid UTF-8 and Rust strings
-        // may contain them. However, it is assumed to never be the
-        // case, when calling this method. Otherwise calling this
-        // method will segfault in release builds.
-        let c_name = CString::new(name);
-        #[cfg(debug_assertions)]
-        let c_name = c_name.unwrap_or_else(|err| panic!("{name:?} contains a nul byte: {err}"));
-        #[cfg(not(debug_assertions))]
-        let c_name = unsafe { c_name.unwrap_unchecked() };
-
+        // may contain them, e.g. when `name` is loaded from a file.
+        // No uniform variable name can contain a nul byte.
+        let c_name = CString::new(name.as_ref()).ok()?;
         Self::get_uniform_location_from_c_str(program, c_name)
     }
 
@@ -88,6 +79,54 @@ impl fmt::Debug for UniformLocation {
     }
 }
 
+/// Number of components per element of a uniform array,
+/// e.g. [`UniformComponents::Three`] for a `vec3[]`.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum UniformComponents {
+    One = 1,
+    Two = 2,
+    Three = 3,
+    Four = 4,
+}
+
+impl Shader<'_> {
+    /// Set a uniform array of `values.len() / components` elements,
+    /// e.g. a dynamic number of `vec3` for [`UniformComponents::Three`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `values` is not a multiple of `components`.
+    pub fn set_uniform_f32_slice(
+        &self,
+        loc: UniformLocation,
+        values: &[f32],
+        components: UniformComponents,
+    ) {
+        let n = components as usize;
+        let count = values.len() / n;
+        assert_eq!(
+            count * n,
+            values.len(),
+            "length is not a multiple of {n} components"
+        );
+
+        let f = match components {
+            UniformComponents::One => gl::ProgramUniform1fv,
+            UniformComponents::Two => gl::ProgramUniform2fv,
+            UniformComponents::Three => gl::ProgramUniform3fv,
+            UniformComponents::Four => gl::ProgramUniform4fv,
+        };
+        unsafe {
+            f(
+                self.gl_handle(),
+                loc.0 as i32,
+                count as i32,
+                values.as_ptr(),
+            );
+        }
+    }
+}
+
 pub trait SetUniform<T>
 where
     T: Copy,
@@ -0,0 +1,164 @@
+pub mod prelude {
+    pub use super::Input;
+}
+
+use std::collections::HashSet;
+
+use glfw::{Action, Key, MouseButton, Window, WindowEvent};
+
+/// Tracks the state of the cursor, mouse buttons, and keys,
+/// updated from the [`WindowEvent`]s polled each frame.
+#[derive(Clone, Default, Debug)]
+pub struct Input {
+    cursor_pos: (f64, f64),
+    prev_cursor_pos: (f64, f64),
+    buttons: HashSet<MouseButton>,
+    keys: HashSet<Key>,
+    pressed_keys: HashSet<Key>,
+    released_keys: HashSet<Key>,
+}
+
+impl Input {
+    pub fn new(wnd: &Window) -> Self {
+        let cursor_pos = wnd.get_cursor_pos();
+        Self {
+            cursor_pos,
+            prev_cursor_pos: cursor_pos,
+            ..Self::default()
+        }
+    }
+
+    /// Must be called at the start of every frame,
+    /// before calling [`handle_event()`](Self::handle_event).
+    pub fn begin_frame(&mut self) {
+        self.prev_cursor_pos = self.cursor_pos;
+        self.pressed_keys.clear();
+        self.released_keys.clear();
+    }
+
+    pub fn handle_event(&mut self, evt: &WindowEvent) {
+        match *evt {
+            WindowEvent::CursorPos(x, y) => {
+                self.cursor_pos = (x, y);
+            }
+            WindowEvent::MouseButton(button, Action::Press, _) => {
+                self.buttons.insert(button);
+            }
+            WindowEvent::MouseButton(button, Action::Release, _) => {
+                self.buttons.remove(&button);
+            }
+            // `Action::Repeat` is ignored, as the key is already down
+            WindowEvent::Key(key, _, Action::Press, _) if !self.keys.contains(&key) => {
+                self.keys.insert(key);
+                self.pressed_keys.insert(key);
+            }
+            WindowEvent::Key(key, _, Action::Release, _) if self.keys.contains(&key) => {
+                self.keys.remove(&key);
+                self.released_keys.insert(key);
+            }
+            _ => {}
+        }
+    }
+
+    #[inline]
+    pub fn cursor_pos(&self) -> (f64, f64) {
+        self.cursor_pos
+    }
+
+    /// Returns how far the cursor moved since the previous frame.
+    #[inline]
+    pub fn cursor_delta(&self) -> (f64, f64) {
+        (
+            self.cursor_pos.0 - self.prev_cursor_pos.0,
+            self.cursor_pos.1 - self.prev_cursor_pos.1,
+        )
+    }
+
+    #[inline]
+    pub fn is_button_down(&self, button: MouseButton) -> bool {
+        self.buttons.contains(&button)
+    }
+
+    /// Returns `true` while `key` is held down.
+    #[inline]
+    pub fn is_key_down(&self, key: Key) -> bool {
+        self.keys.contains(&key)
+    }
+
+    /// Returns `true` if `key` went down this frame.
+    #[inline]
+    pub fn was_key_pressed(&self, key: Key) -> bool {
+        self.pressed_keys.contains(&key)
+    }
+
+    /// Returns `true` if `key` went up this frame.
+    #[inline]
+    pub fn was_key_released(&self, key: Key) -> bool {
+        self.released_keys.contains(&key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glfw::{Action, Key, Modifiers, WindowEvent};
+
+    use super::Input;
+
+    fn key(key: Key, action: Action) -> WindowEvent {
+        WindowEvent::Key(key, 0, action, Modifiers::empty())
+    }
+
+    #[test]
+    fn press_repeat_release() {
+        let mut input = Input::default();
+
+        input.begin_frame();
+        input.handle_event(&key(Key::W, Action::Press));
+        assert!(input.is_key_down(Key::W));
+        assert!(input.was_key_pressed(Key::W));
+
+        input.begin_frame();
+        input.handle_event(&key(Key::W, Action::Repeat));
+        assert!(input.is_key_down(Key::W));
+        assert!(!input.was_key_pressed(Key::W));
+        assert!(!input.was_key_released(Key::W));
+
+        input.begin_frame();
+        input.handle_event(&key(Key::W, Action::Release));
+        assert!(!input.is_key_down(Key::W));
+        assert!(!input.was_key_pressed(Key::W));
+        assert!(input.was_key_released(Key::W));
+    }
+
+    #[test]
+    fn press_and_release_within_frame() {
+        let mut input = Input::default();
+
+        input.begin_frame();
+        input.handle_event(&key(Key::Space, Action::Press));
+        input.handle_event(&key(Key::Space, Action::Release));
+        assert!(!input.is_key_down(Key::Space));
+        assert!(input.was_key_pressed(Key::Space));
+        assert!(input.was_key_released(Key::Space));
+    }
+
+    #[test]
+    fn begin_frame_clears_per_frame_state() {
+        let mut input = Input::default();
+
+        input.begin_frame();
+        input.handle_event(&key(Key::A, Action::Press));
+        input.handle_event(&key(Key::B, Action::Press));
+        input.handle_event(&key(Key::B, Action::Release));
+        input.handle_event(&WindowEvent::CursorPos(10.0, 20.0));
+        assert_eq!(input.cursor_delta(), (10.0, 20.0));
+
+        input.begin_frame();
+        assert!(input.is_key_down(Key::A));
+        assert!(!input.was_key_pressed(Key::A));
+        assert!(!input.was_key_pressed(Key::B));
+        assert!(!input.was_key_released(Key::B));
+        assert_eq!(input.cursor_pos(), (10.0, 20.0));
+        assert_eq!(input.cursor_delta(), (0.0, 0.0));
+    }
+}
@@ -4,15 +4,23 @@
 
 pub mod prelude {
     pub use crate::app::prelude::*;
+    pub use crate::debug_output::prelude::*;
     pub use crate::gl45::prelude::*;
+    pub use crate::input::prelude::*;
+    pub use crate::stats::prelude::*;
 }
 
 mod app;
 mod debug_output;
 mod gl45;
+mod input;
+mod stats;
 
 pub use crate::app::*;
+pub use crate::debug_output::DebugSeverity;
 pub use crate::gl45::*;
+pub use crate::input::*;
+pub use crate::stats::*;
 
 /// Run an [`App`] with the default [`AppOptions`], i.e. the same as:
 ///
@@ -0,0 +1,166 @@
+pub mod prelude {
+    pub use super::FrameStats;
+}
+
+use std::collections::VecDeque;
+
+/// Frame times in seconds over a sliding window of the most recent
+/// frames, e.g. for displaying an FPS overlay.
+///
+/// When running an [`App`](crate::App), the stats are updated at the
+/// end of each frame, and can be accessed from `update()` and `draw()`
+/// using [`RenderingContext::frame_stats()`](crate::RenderingContext::frame_stats).
+#[derive(Clone, Debug)]
+pub struct FrameStats {
+    frame_times: VecDeque<f64>,
+    window: usize,
+}
+
+impl FrameStats {
+    pub const DEFAULT_WINDOW: usize = 120;
+
+    /// Create stats over the most recent `window` frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is `0`.
+    pub fn new(window: usize) -> Self {
+        assert_ne!(window, 0, "window must be at least 1 frame");
+        Self {
+            frame_times: VecDeque::with_capacity(window),
+            window,
+        }
+    }
+
+    /// Record the duration of a frame in seconds, discarding
+    /// the oldest frame if the window is full.
+    pub fn push(&mut self, frame_time: f64) {
+        if self.frame_times.len() == self.window {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time);
+    }
+
+    /// Returns the duration of the most recent frame,
+    /// or `0.0` if no frames are recorded.
+    #[inline]
+    pub fn frame_time(&self) -> f64 {
+        self.frame_times.back().copied().unwrap_or(0.0)
+    }
+
+    #[inline]
+    pub fn min_frame_time(&self) -> f64 {
+        self.frame_times
+            .iter()
+            .copied()
+            .reduce(f64::min)
+            .unwrap_or(0.0)
+    }
+
+    #[inline]
+    pub fn max_frame_time(&self) -> f64 {
+        self.frame_times
+            .iter()
+            .copied()
+            .reduce(f64::max)
+            .unwrap_or(0.0)
+    }
+
+    #[inline]
+    pub fn avg_frame_time(&self) -> f64 {
+        if self.frame_times.is_empty() {
+            0.0
+        } else {
+            self.frame_times.iter().sum::<f64>() / self.frame_times.len() as f64
+        }
+    }
+
+    /// Returns the frames per second, based on the
+    /// average frame time, or `0.0` if unknown.
+    #[inline]
+    pub fn fps(&self) -> f64 {
+        let avg = self.avg_frame_time();
+        if avg > 0.0 {
+            1.0 / avg
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the number of recorded frames, at most the window size.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frame_times.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frame_times.is_empty()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.frame_times.clear();
+    }
+}
+
+impl Default for FrameStats {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrameStats;
+
+    #[test]
+    fn empty() {
+        let stats = FrameStats::default();
+        assert!(stats.is_empty());
+        assert_eq!(stats.frame_time(), 0.0);
+        assert_eq!(stats.min_frame_time(), 0.0);
+        assert_eq!(stats.max_frame_time(), 0.0);
+        assert_eq!(stats.avg_frame_time(), 0.0);
+        assert_eq!(stats.fps(), 0.0);
+    }
+
+    #[test]
+    fn values() {
+        let mut stats = FrameStats::new(4);
+        for frame_time in [0.02, 0.01, 0.04, 0.03] {
+            stats.push(frame_time);
+        }
+
+        assert_eq!(stats.len(), 4);
+        assert_eq!(stats.frame_time(), 0.03);
+        assert_eq!(stats.min_frame_time(), 0.01);
+        assert_eq!(stats.max_frame_time(), 0.04);
+        assert!((stats.avg_frame_time() - 0.025).abs() < 1e-12);
+        assert!((stats.fps() - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn window_eviction() {
+        let mut stats = FrameStats::new(2);
+        stats.push(1.0);
+        stats.push(0.5);
+        stats.push(0.25);
+
+        // The oldest frame time `1.0` is evicted
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats.max_frame_time(), 0.5);
+        assert_eq!(stats.min_frame_time(), 0.25);
+        assert_eq!(stats.avg_frame_time(), 0.375);
+
+        stats.clear();
+        assert!(stats.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_window() {
+        FrameStats::new(0);
+    }
+}